@@ -2,7 +2,7 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
-pub use humanbyte::HumanByte;
+pub use humanbyte::{bytes, HumanByte, HumanByteRate};
 
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
@@ -13,6 +13,11 @@ use arbitrary::Arbitrary;
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub struct ByteScale(pub u64);
 
+/// A transfer rate in bytes per second, e.g. `ByteScale::mib(100) / Duration::from_secs(8)`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Default, HumanByteRate)]
+#[humanbyte(size = "ByteScale")]
+pub struct ByteScaleRate(pub u64);
+
 // Re-export for doc tests
 #[doc(hidden)]
 pub use self::ByteScale as _doc_ByteScale;
@@ -20,7 +25,7 @@ pub use self::ByteScale as _doc_ByteScale;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use humanbyte::{Format, B, KB, MB};
+    use humanbyte::{ByteDelta, Format, ParseError, ParseErrorKind, B, KB, MB};
 
     #[test]
     fn test_arithmetic_op() {
@@ -41,6 +46,7 @@ mod tests {
         assert_eq!(x.as_u64(), 2_200_000);
     }
     #[test]
+    #[allow(clippy::unnecessary_cast)]
     fn test_arithmetic_primitives() {
         let mut x = ByteScale::mb(1);
 
@@ -67,6 +73,882 @@ mod tests {
         assert_eq!(x.as_u64(), 3_000_011);
     }
 
+    #[test]
+    fn test_checked_arithmetic() {
+        let free = ByteScale::mb(1);
+        let used = ByteScale::mb(2);
+
+        assert_eq!(free.checked_sub(used), None);
+        assert_eq!(used.checked_sub(free), Some(ByteScale::mb(1)));
+        assert_eq!(free.saturating_sub(used), ByteScale(0));
+        assert_eq!(free.wrapping_sub(used), ByteScale(u64::MAX - 999_999));
+        assert_eq!(
+            free.overflowing_sub(used),
+            (ByteScale(u64::MAX - 999_999), true)
+        );
+
+        assert_eq!(ByteScale(u64::MAX).checked_add(ByteScale(1)), None);
+        assert_eq!(
+            ByteScale(u64::MAX).saturating_add(ByteScale(1)),
+            ByteScale(u64::MAX)
+        );
+        assert_eq!(ByteScale(u64::MAX).wrapping_add(ByteScale(2)), ByteScale(1));
+        assert_eq!(free.overflowing_add(used), (ByteScale::mb(3), false));
+
+        assert_eq!(ByteScale::eib(8).checked_mul(2), None);
+        assert_eq!(ByteScale::eib(8).saturating_mul(2), ByteScale(u64::MAX));
+        assert_eq!(ByteScale::eib(8).wrapping_mul(2), ByteScale(0));
+        assert_eq!(ByteScale::eib(8).overflowing_mul(2), (ByteScale(0), true));
+
+        assert_eq!(used.checked_div(0), None);
+        assert_eq!(used.checked_div(4), Some(ByteScale::kb(500)));
+        assert_eq!(used.saturating_div(2), free);
+        assert_eq!(used.wrapping_div(2), free);
+        assert_eq!(used.overflowing_div(2), (free, false));
+    }
+
+    #[test]
+    fn test_division() {
+        let file = ByteScale::mib(10);
+        let chunk = ByteScale::mib(4);
+
+        assert_eq!(file / chunk, 2);
+        assert_eq!(file.div_ceil(chunk), 3);
+        assert_eq!(file % chunk, ByteScale::mib(2));
+        assert_eq!(file / 4u8, ByteScale::kib(2560));
+        assert_eq!(file % 3u32, ByteScale(1));
+
+        let mut x = file;
+        x /= 2u64;
+        assert_eq!(x, ByteScale::mib(5));
+        x %= chunk;
+        assert_eq!(x, ByteScale::mib(1));
+        x %= 1000u16;
+        assert_eq!(x, ByteScale(576));
+
+        let quota = ByteScale::gib(4);
+        assert_eq!(ByteScale::gib(1).ratio(&quota), 0.25);
+        assert_eq!(ByteScale::gib(3).percentage(&quota), 75.0);
+    }
+
+    #[test]
+    fn test_bit_units() {
+        use humanbyte::Unit;
+
+        assert_eq!(ByteScale::mbit(8), ByteScale::mb(1));
+        assert_eq!(ByteScale::kibit(1), ByteScale(128));
+        assert_eq!(ByteScale::gbit(1), ByteScale(125_000_000));
+        assert_eq!(ByteScale::checked_eibit(u64::MAX), None);
+        assert_eq!(ByteScale128::qibit(8), ByteScale128::qib(1));
+
+        assert_eq!(Unit::Bit.bits(), 1);
+        assert_eq!(u128::from(Unit::Bit), 0);
+        assert_eq!(u128::from(Unit::MebiBit), 131_072);
+        assert_eq!(Unit::KiloByte.bits(), 8_000);
+        assert!(Unit::GibiBit.is_bit() && !Unit::GibiByte.is_bit());
+
+        // without case sensitivity, bits need the `bit` symbol
+        assert_eq!("100 Mbit".parse::<ByteScale>(), Ok(ByteScale::mbit(100)));
+        assert_eq!("100 Mb".parse::<ByteScale>(), Ok(ByteScale::mb(100)));
+        assert_eq!("1.5 kibit".parse::<ByteScale>(), Ok(ByteScale(192)));
+        assert_eq!("12 bit".parse::<ByteScale>(), Ok(ByteScale(1)));
+
+        for (input, expected) in [
+            ("100 Mb", ByteScale::mbit(100)),
+            ("100 MB", ByteScale::mb(100)),
+            ("100 Mbit", ByteScale::mbit(100)),
+            ("1 kB", ByteScale::kb(1)),
+            ("1 KB", ByteScale::kb(1)),
+            ("1 kb", ByteScale::kbit(1)),
+            ("1 KiB", ByteScale::kib(1)),
+            ("1 Kib", ByteScale::kibit(1)),
+            ("1 Gi", ByteScale::gib(1)),
+            ("16 b", ByteScale(2)),
+            ("16 B", ByteScale(16)),
+        ] {
+            assert_eq!(
+                humanbyte::parse_case_sensitive::<u64>(input).map(ByteScale),
+                Ok(expected),
+                "{}",
+                input
+            );
+        }
+        for input in ["1 mb", "1 kiB", "1 gB", "1 iB", "1 MBit"] {
+            let error = humanbyte::parse_case_sensitive::<u64>(input).unwrap_err();
+            assert_eq!(
+                (error.kind(), error.span()),
+                (ParseErrorKind::UnknownUnit, 2..input.len()),
+                "{}",
+                input
+            );
+        }
+
+        let size = ByteScale(12_400_000);
+        assert_eq!(size.to_string_as(Format::SIBits), "99.2 Mbit");
+        assert_eq!(size.to_string_as(Format::IECBits), "94.6 Mibit");
+        assert_eq!(ByteScale(100).to_string_as(Format::SIBits), "800 bit");
+        assert_eq!(ByteScale(127).to_string_as(Format::IECBits), "1016 bit");
+        assert_eq!(ByteScale(128).to_string_as(Format::IECBits), "1.0 Kibit");
+        let exact = humanbyte::to_string_exact(ByteScale::mb(1).as_u64(), Format::SIBits);
+        assert_eq!(exact, "8 Mbit");
+        assert_eq!(exact.parse(), Ok(ByteScale::mb(1)));
+        let exact = humanbyte::to_string_exact(u128::MAX, Format::IECBits);
+        assert_eq!(exact.parse(), Ok(ByteScale128(u128::MAX)));
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        use humanbyte::Parser;
+
+        for (input, expected) in [
+            ("1 B", ByteScale(1)),
+            ("1024", ByteScale(1024)),
+            ("1.5 kB", ByteScale(1500)),
+            ("1 KiB", ByteScale::kib(1)),
+            ("2 MB", ByteScale::mb(2)),
+            ("2 MiB", ByteScale::mib(2)),
+            ("8 Mbit", ByteScale::mb(1)),
+            ("1 Kibit", ByteScale(128)),
+        ] {
+            assert_eq!(ByteScale::parse_strict(input), Ok(expected), "{}", input);
+        }
+        for (input, kind) in [
+            ("1 k", ParseErrorKind::DisallowedUnit),
+            ("1 m", ParseErrorKind::DisallowedUnit),
+            ("1 Ki", ParseErrorKind::DisallowedUnit),
+            ("1 mib", ParseErrorKind::DisallowedUnit),
+            ("1 KB", ParseErrorKind::DisallowedUnit),
+            ("1 Mb", ParseErrorKind::DisallowedUnit),
+            ("1 b", ParseErrorKind::DisallowedUnit),
+            ("1 XB", ParseErrorKind::UnknownUnit),
+        ] {
+            let error = ByteScale::parse_strict(input).unwrap_err();
+            assert_eq!(
+                (error.kind(), error.span()),
+                (kind, 2..input.len()),
+                "{}",
+                input
+            );
+        }
+        // the lenient parser is unchanged
+        assert_eq!("1 mib".parse(), Ok(ByteScale::mib(1)));
+
+        let iec_only = Parser::strict().allow_si(false);
+        assert_eq!(
+            ByteScale::parse_with(&iec_only, "4 GiB"),
+            Ok(ByteScale::gib(4))
+        );
+        assert_eq!(ByteScale::parse_with(&iec_only, "4 B"), Ok(ByteScale(4)));
+        for input in ["4 GB", "4 kbit"] {
+            let error = ByteScale::parse_with(&iec_only, input).unwrap_err();
+            assert_eq!(error.kind(), ParseErrorKind::DisallowedUnit, "{}", input);
+        }
+        let lenient_iec = Parser::new().allow_si(false);
+        assert_eq!(
+            ByteScale::parse_with(&lenient_iec, "4 gib"),
+            Ok(ByteScale::gib(4))
+        );
+        assert!(ByteScale::parse_with(&lenient_iec, "4 g").is_err());
+        assert_eq!(Parser::default(), Parser::new());
+    }
+
+    /// Parses every input with `convention`, expecting either a size or an error kind.
+    fn check_convention(
+        convention: humanbyte::Convention,
+        cases: &[(&str, Result<u64, ParseErrorKind>)],
+    ) {
+        let parser = humanbyte::Parser::new().convention(convention);
+        for (input, expected) in cases {
+            let parsed = parser.parse::<u64>(input).map_err(|error| error.kind());
+            assert_eq!(parsed, *expected, "{:?} {:?}", convention, input);
+        }
+    }
+
+    #[test]
+    fn test_convention_standard() {
+        check_convention(
+            humanbyte::Convention::Standard,
+            &[
+                ("1", Ok(1)),
+                ("1K", Ok(1_000)),
+                ("1KB", Ok(1_000)),
+                ("1kb", Ok(1_000)),
+                ("1KiB", Ok(1_024)),
+                ("1Ki", Ok(1_024)),
+                ("1g", Ok(1_000_000_000)),
+                ("1.5 MB", Ok(1_500_000)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_convention_jedec() {
+        check_convention(
+            humanbyte::Convention::Jedec,
+            &[
+                ("1", Ok(1)),
+                ("1K", Ok(1_024)),
+                ("1KB", Ok(1_024)),
+                ("1kb", Ok(1_024)),
+                ("1KiB", Ok(1_024)),
+                ("1g", Ok(1 << 30)),
+                ("512m", Ok(512 << 20)),
+                ("1.5 MB", Ok(1_572_864)),
+                ("8 Mbit", Ok(1 << 20)),
+                ("1 XB", Err(ParseErrorKind::UnknownUnit)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_convention_kubernetes() {
+        check_convention(
+            humanbyte::Convention::Kubernetes,
+            &[
+                ("128974848", Ok(128_974_848)),
+                ("129e6", Ok(129_000_000)),
+                ("129E6", Ok(129_000_000)),
+                ("1.5e3", Ok(1_500)),
+                ("129M", Ok(129_000_000)),
+                ("123Mi", Ok(128_974_848)),
+                ("1k", Ok(1_000)),
+                ("1Ki", Ok(1_024)),
+                ("2E", Ok(2_000_000_000_000_000_000)),
+                ("1Ei", Ok(1 << 60)),
+                ("1500m", Ok(1)),
+                ("999m", Ok(0)),
+                ("5000000u", Ok(5)),
+                ("1K", Err(ParseErrorKind::UnknownUnit)),
+                // canonical symbols, as written by `Display`
+                ("1KiB", Ok(1_024)),
+                ("1.5 MB", Ok(1_500_000)),
+                ("8 kbit", Ok(1_000)),
+                ("100 B", Ok(100)),
+                ("1kib", Err(ParseErrorKind::UnknownUnit)),
+                ("1KB", Err(ParseErrorKind::UnknownUnit)),
+                ("1e99", Err(ParseErrorKind::Overflow)),
+                ("1.5e38", Err(ParseErrorKind::Overflow)),
+                ("1e", Err(ParseErrorKind::UnknownUnit)),
+            ],
+        );
+
+        // a fraction of the largest exponent still fits into a `u128`
+        let parser = humanbyte::Parser::new().convention(humanbyte::Convention::Kubernetes);
+        assert_eq!(parser.parse::<u128>("0.9e38"), Ok(9 * 10u128.pow(37)));
+        assert_eq!(parser.parse::<u128>("1.5e38"), Ok(15 * 10u128.pow(37)));
+        assert_eq!(
+            parser.parse::<u128>("3.5e38").unwrap_err().kind(),
+            ParseErrorKind::Overflow
+        );
+    }
+
+    #[test]
+    fn test_convention_gnu() {
+        check_convention(
+            humanbyte::Convention::Gnu,
+            &[
+                ("1", Ok(1)),
+                ("1K", Ok(1_024)),
+                ("1k", Ok(1_024)),
+                ("1KB", Ok(1_000)),
+                ("1kB", Ok(1_000)),
+                ("1KiB", Ok(1_024)),
+                ("1M", Ok(1 << 20)),
+                ("1MB", Ok(1_000_000)),
+                ("1MiB", Ok(1 << 20)),
+                ("1G", Ok(1 << 30)),
+                ("1GB", Ok(1_000_000_000)),
+                ("1m", Err(ParseErrorKind::UnknownUnit)),
+                ("1mb", Err(ParseErrorKind::UnknownUnit)),
+                ("1b", Err(ParseErrorKind::UnknownUnit)),
+                ("1Mb", Err(ParseErrorKind::UnknownUnit)),
+                ("1kbit", Err(ParseErrorKind::UnknownUnit)),
+                ("1Kib", Err(ParseErrorKind::UnknownUnit)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_convention_attribute() {
+        #[derive(PartialEq, HumanByte)]
+        #[humanbyte(convention = "gnu")]
+        struct DdSize(u64);
+
+        #[derive(PartialEq, HumanByte)]
+        #[humanbyte(convention = "kubernetes")]
+        struct Quantity(u64);
+
+        assert_eq!("4K".parse(), Ok(DdSize(4_096)));
+        assert_eq!("4KB".parse(), Ok(DdSize(4_000)));
+        assert_eq!("64Mi".parse(), Ok(Quantity::mib(64)));
+        assert_eq!("64e6".parse(), Ok(Quantity::mb(64)));
+        assert!("64mib".parse::<Quantity>().is_err());
+        // displayed sizes parse back
+        assert_eq!(Quantity::mib(64).to_string().parse(), Ok(Quantity::mib(64)));
+        assert_eq!(Quantity::b(100).to_string().parse(), Ok(Quantity::b(100)));
+        // the default convention is unchanged
+        assert_eq!("4K".parse(), Ok(ByteScale(4_000)));
+    }
+
+    #[test]
+    fn test_convention_strict() {
+        #[derive(PartialEq, HumanByte)]
+        #[humanbyte(convention = "JEDEC")]
+        struct Ram(u64);
+
+        #[derive(PartialEq, HumanByte)]
+        #[humanbyte(convention = "gnu")]
+        struct DdSize(u64);
+
+        assert_eq!(Ram::parse_strict("1 KB"), Ok(Ram(1_024)));
+        assert_eq!(Ram::parse_strict("1 kB"), Ok(Ram(1_024)));
+        assert_eq!(Ram::parse_strict("1 KiB"), Ok(Ram(1_024)));
+        for input in ["1 kib", "1 KILOBYTE", "1 kb", "1 K"] {
+            let error = Ram::parse_strict(input).unwrap_err();
+            assert_eq!(error.kind(), ParseErrorKind::DisallowedUnit, "{input}");
+        }
+        // the lenient parser still accepts them
+        assert_eq!("1 kib".parse(), Ok(Ram(1_024)));
+
+        assert_eq!(DdSize::parse_strict("4K"), Ok(DdSize(4_096)));
+        assert_eq!(DdSize::parse_strict("4M"), Ok(DdSize(4_194_304)));
+        assert_eq!(DdSize::parse_strict("4KB"), Ok(DdSize(4_000)));
+        assert_eq!(DdSize::parse_strict("4KiB"), Ok(DdSize(4_096)));
+        for input in ["4k", "4Mi"] {
+            let error = DdSize::parse_strict(input).unwrap_err();
+            assert_eq!(error.kind(), ParseErrorKind::DisallowedUnit, "{input}");
+        }
+        assert_eq!("4k".parse(), Ok(DdSize(4_096)));
+    }
+
+    /// A French unit table, using `octet` for bytes and `Ko`/`Kio` style symbols.
+    struct French;
+
+    impl humanbyte::UnitNames for French {
+        fn name(&self, unit: humanbyte::Unit, number: &str) -> &str {
+            use humanbyte::Unit;
+
+            // French treats amounts below two as singular
+            let singular = number.parse::<f64>().map_or(false, |number| number < 2.0);
+            match unit {
+                Unit::Byte if singular => "octet",
+                Unit::Byte => "octets",
+                Unit::KiloByte => "Ko",
+                Unit::MegaByte => "Mo",
+                Unit::KibiByte => "Kio",
+                Unit::MebiByte => "Mio",
+                unit => unit.symbol(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_names() {
+        use humanbyte::{to_string_with_names, LongNames};
+
+        assert_eq!(ByteScale(1).to_string_as(Format::IECLong), "1 byte");
+        assert_eq!(ByteScale(2).to_string_as(Format::IECLong), "2 bytes");
+        assert_eq!(ByteScale(0).to_string_as(Format::SILong), "0 bytes");
+        assert_eq!(
+            ByteScale(1536).to_string_as(Format::IECLong),
+            "1.5 kibibytes"
+        );
+        assert_eq!(
+            ByteScale::kib(1).to_string_as(Format::IECLong),
+            "1.0 kibibytes"
+        );
+        assert_eq!(
+            ByteScale::mb(3).to_string_as(Format::SILong),
+            "3.0 megabytes"
+        );
+        assert_eq!(
+            to_string_with_names(1024u64, Format::IEC, 0, &LongNames),
+            "1 kibibyte"
+        );
+        assert_eq!(
+            to_string_with_names(125u64, Format::SIBits, 0, &LongNames),
+            "1 kilobit"
+        );
+        assert_eq!(
+            humanbyte::to_string_exact(ByteScale::mib(3).as_u64(), Format::IECLong),
+            "3 mebibytes"
+        );
+        assert_eq!(
+            humanbyte::to_string_exact(ByteScale::mib(1).as_u64(), Format::IECLong),
+            "1 mebibyte"
+        );
+
+        // long names parse back
+        assert_eq!("1.5 kibibytes".parse(), Ok(ByteScale(1536)));
+        assert_eq!("1 Megabyte".parse(), Ok(ByteScale::mb(1)));
+        assert_eq!("16 bits".parse(), Ok(ByteScale(2)));
+        let error = ByteScale::parse_strict("1 kibibyte").unwrap_err();
+        assert_eq!(error.kind(), ParseErrorKind::DisallowedUnit);
+
+        #[derive(HumanByte)]
+        #[humanbyte(format = "iec-long")]
+        struct Spoken(u64);
+        assert_eq!(Spoken(1).to_string(), "1 byte");
+        assert_eq!(Spoken::mib(12).to_string(), "12.0 mebibytes");
+        assert_eq!(format!("{:#.0}", Spoken::mb(12)), "12 megabytes");
+
+        assert_eq!(
+            to_string_with_names(1u64, Format::SI, 1, &French),
+            "1 octet"
+        );
+        assert_eq!(
+            to_string_with_names(5u64, Format::SI, 1, &French),
+            "5 octets"
+        );
+        assert_eq!(
+            to_string_with_names(1_500u64, Format::SI, 1, &French),
+            "1.5 Ko"
+        );
+        assert_eq!(
+            to_string_with_names(1u64 << 30, Format::IEC, 0, &French),
+            "1 GiB"
+        );
+    }
+
+    #[test]
+    fn test_number_style() {
+        use humanbyte::{to_string_with_style, LongNames, NumberStyle, Parser, Symbols};
+
+        let european = NumberStyle::new()
+            .decimal_separator(',')
+            .group_separator(Some('\u{2009}'));
+        assert_eq!(
+            to_string_with_style(1536u64, Format::IEC, 1, &european, &Symbols),
+            "1,5 KiB"
+        );
+        assert_eq!(
+            to_string_with_style(999u64, Format::SI, 1, &european, &Symbols),
+            "999 B"
+        );
+        assert_eq!(
+            to_string_with_style(1_234_567u64, Format::IEC, 2, &european, &LongNames),
+            "1,18 mebibytes"
+        );
+        assert_eq!(
+            to_string_with_style(u64::MAX, Format::SI, 0, &european, &Symbols),
+            "18 EB"
+        );
+        assert_eq!(
+            to_string_with_style(1u128 << 110, Format::IEC, 1, &european, &Symbols),
+            "1\u{2009}024,0 QiB"
+        );
+        let grouped = NumberStyle::new().group_separator(Some('_'));
+        assert_eq!(
+            to_string_with_style(1_234_567u64, Format::IEC, 0, &grouped, &Symbols),
+            "1 MiB"
+        );
+        assert_eq!(
+            to_string_with_style(1u128 << 112, Format::IECBits, 3, &grouped, &Symbols),
+            "32_768.000 Qibit"
+        );
+        assert_eq!(
+            to_string_with_style(1536u64, Format::SI, 1, &NumberStyle::default(), &Symbols),
+            ByteScale(1536).to_string_as(Format::SI)
+        );
+
+        let parser = Parser::new().number_style(european);
+        assert_eq!(
+            ByteScale::parse_with(&parser, "1,5 MiB"),
+            Ok(ByteScale(1_572_864))
+        );
+        assert_eq!(
+            ByteScale::parse_with(&parser, "1\u{2009}000 B"),
+            Ok(ByteScale(1_000))
+        );
+        assert_eq!(
+            ByteScale::parse_with(&parser, "12\u{2009}345\u{2009}678,9 KB"),
+            Ok(ByteScale(12_345_678_900))
+        );
+        let error = ByteScale::parse_with(&parser, "1.5 MiB").unwrap_err();
+        assert_eq!(
+            (error.kind(), error.span()),
+            (ParseErrorKind::TrailingGarbage, 1..7)
+        );
+
+        for (separator, input) in [
+            (',', "1,000 B"),
+            ('_', "1_000 B"),
+            ('\'', "1'000 B"),
+            (' ', "1 000 B"),
+            ('\u{2009}', "1\u{2009}000 B"),
+        ] {
+            let parser =
+                Parser::new().number_style(NumberStyle::new().group_separator(Some(separator)));
+            assert_eq!(
+                ByteScale::parse_with(&parser, input),
+                Ok(ByteScale(1_000)),
+                "{}",
+                input
+            );
+        }
+
+        let parser = Parser::new().number_style(NumberStyle::new().group_separator(Some(',')));
+        assert_eq!(
+            ByteScale::parse_with(&parser, "1,234.5 KB"),
+            Ok(ByteScale(1_234_500))
+        );
+        for (input, span) in [("1,5 KB", 0..3), ("1,23,456 B", 0..4), ("1234,567 B", 0..4)] {
+            let error = ByteScale::parse_with(&parser, input).unwrap_err();
+            assert_eq!(
+                (error.kind(), error.span()),
+                (ParseErrorKind::InvalidNumber, span),
+                "{}",
+                input
+            );
+        }
+
+        // the default parser is unchanged
+        assert!("1,5 MiB".parse::<ByteScale>().is_err());
+        assert!("1 000 B".parse::<ByteScale>().is_err());
+    }
+
+    #[test]
+    fn test_to_string_boundaries() {
+        use humanbyte::{EB, GB, PB, TB};
+
+        // exact powers of the unit always use the larger unit
+        for (bytes, expected) in [
+            (KB - 1, "999 B"),
+            (KB, "1.0 kB"),
+            (MB - 1, "1000.0 kB"),
+            (MB, "1.0 MB"),
+            (GB, "1.0 GB"),
+            (TB, "1.0 TB"),
+            (PB - 1, "1000.0 TB"),
+            (PB, "1.0 PB"),
+            (EB, "1.0 EB"),
+        ] {
+            assert_eq!(humanbyte::to_string(bytes, Format::SI), expected);
+        }
+        assert_eq!(humanbyte::to_string(u64::MAX, Format::IEC), "16.0 EiB");
+        assert_eq!(
+            humanbyte::to_string(u128::MAX, Format::SIBits),
+            "2722258935.4 Qbit"
+        );
+
+        // halves round up, and rounding carries into the integer part
+        assert_eq!(ByteScale(1_280).to_string(), "1.3 KiB");
+        assert_eq!(format!("{:.2}", ByteScale(1_152)), "1.13 KiB");
+        assert_eq!((ByteScale::mib(1) - 1u64).to_string(), "1024.0 KiB");
+        assert_eq!(format!("{:.0}", ByteScale::kib(1) + 512u64), "2 KiB");
+    }
+
+    #[test]
+    fn test_bytes_literal() {
+        const LIMIT: ByteScale = bytes!(ByteScale, "4.5 MiB");
+        const RAW: u64 = bytes!("1.5 kB");
+        assert_eq!(LIMIT, ByteScale(4_718_592));
+        assert_eq!(RAW, 1_500);
+        assert_eq!(bytes!(ByteScale128, "1 QiB"), ByteScale128(1 << 100));
+
+        // the same grammar as `FromStr`
+        assert_eq!(bytes!(ByteScale, "12 Mbit"), "12 Mbit".parse().unwrap());
+        assert_eq!(bytes!(ByteScale, ".5 gb "), ".5 gb ".parse().unwrap());
+        assert!(" .5 gb".parse::<ByteScale>().is_err());
+        assert_eq!(bytes!(ByteScale, "+3 K"), "+3 K".parse().unwrap());
+        assert_eq!(
+            bytes!(ByteScale, "2 kibibytes"),
+            "2 kibibytes".parse().unwrap()
+        );
+        assert_eq!(bytes!(ByteScale, "1 bit"), "1 bit".parse().unwrap());
+        assert_eq!(bytes!(ByteScale, "7"), ByteScale(7));
+    }
+
+    #[test]
+    fn test_parsers_agree() {
+        // runs each size through `FromStr`, the const parser and the `bytes!` macro
+        macro_rules! check {
+            ($($input:literal),* $(,)?) => {$(
+                let expected = humanbyte::parse::<u128>($input).unwrap();
+                assert_eq!(humanbyte::parse_bytes_u128($input), Ok(expected), "{:?}", $input);
+                let literal: u128 = bytes!($input);
+                assert_eq!(literal, expected, "{:?}", $input);
+            )*};
+        }
+
+        check!(
+            "0",
+            "7",
+            "+3 K",
+            ".5 gb ",
+            "1.5 kB",
+            "0.999999 KiB",
+            "1.0000001 EiB",
+            "12 Mbit",
+            "3 kibibits",
+            "1 bit",
+            "7 bits",
+            "2 kibibytes",
+            "1 megabyte",
+            "18446744073709551615",
+            "1.5 QiB",
+            "0.1 quettabytes",
+        );
+
+        for input in ["", "1 XB", "-1 KiB", "1 KiB/s", ".", "1e3", "1 kibibyte s"] {
+            assert_eq!(
+                humanbyte::parse_bytes_u128(input),
+                humanbyte::parse::<u128>(input).map_err(|error| error.kind()),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_const() {
+        use humanbyte::{parse_bytes, parse_bytes_u128};
+
+        const LIMIT: ByteScale = ByteScale::parse_const("512 MiB");
+        const HUGE: ByteScale128 = ByteScale128::parse_const("1.5 QiB");
+        assert_eq!(LIMIT, ByteScale::mib(512));
+        assert_eq!(HUGE, ByteScale128(3 << 99));
+        assert_eq!(parse_bytes("1.5 kbit"), Ok(187));
+        assert_eq!(parse_bytes_u128("1 quebibyte"), Ok(1 << 100));
+        assert_eq!(parse_bytes("20 EiB"), Err(ParseErrorKind::Overflow));
+
+        for input in [
+            "1.5 MiB",
+            "2 kibibytes",
+            "1 byte",
+            "8 Kibit",
+            "3 k",
+            "7",
+            ".5 GB",
+            "4.7 gb ",
+            "1.",
+            "",
+            "  ",
+            "-1 KB",
+            "abc",
+            "1 XB",
+            "1 KB/s",
+            " 1 KB",
+            "1.2.3 KB",
+            "99999999999 GB",
+        ] {
+            assert_eq!(
+                parse_bytes(input),
+                input
+                    .parse::<ByteScale>()
+                    .map(|size| size.as_u64())
+                    .map_err(|error| error.kind()),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown unit")]
+    fn test_parse_const_panics() {
+        ByteScale32::parse_const("1 XB");
+    }
+
+    #[test]
+    fn test_write_to() {
+        use humanbyte::{write_exact, write_to, FormattedSize, MAX_PRECISION};
+
+        let size = ByteScale::kib(1536);
+        let mut out = String::new();
+        size.write_to(&mut out).unwrap();
+        assert_eq!(out, "1.5 MiB");
+
+        out.clear();
+        write_exact(&mut out, 1536u64, Format::IEC).unwrap();
+        assert_eq!(out, "1536 B");
+
+        let formatted = size.formatted(Format::SI);
+        assert_eq!(formatted.as_ref(), "1.6 MB");
+        assert_eq!(format!("{:>8}|", formatted), "  1.6 MB|");
+        assert_eq!(
+            FormattedSize::rate(1_500u64, Format::SI, 2).as_str(),
+            "1.50 kB/s"
+        );
+        assert_eq!(
+            FormattedSize::exact(3u64 << 20, Format::IEC).as_str(),
+            "3 MiB"
+        );
+
+        // the precision is capped so that the longest sizes still fit on the stack
+        let longest = FormattedSize::new(u128::MAX, Format::SILong, usize::MAX);
+        let (number, unit) = longest.as_str().split_once(' ').unwrap();
+        assert_eq!(number.split_once('.').unwrap().1.len(), MAX_PRECISION);
+        assert_eq!(unit, "quettabytes");
+        out.clear();
+        write_to(&mut out, u128::MAX, Format::SILong, usize::MAX).unwrap();
+        assert_eq!(out, longest.as_str());
+    }
+
+    #[test]
+    fn test_signed_delta() {
+        let yesterday = ByteScale::gib(10);
+        let today = ByteScale::gib(11) + ByteScale::mib(200);
+
+        assert_eq!(yesterday.abs_diff(today), ByteScale::mib(1224));
+        assert_eq!(today.abs_diff(yesterday), ByteScale::mib(1224));
+        assert_eq!(today.signed_sub(yesterday).to_string(), "+1.2 GiB");
+        assert_eq!(yesterday.signed_sub(today).to_string(), "-1.2 GiB");
+        assert_eq!(
+            ByteScale(0).signed_sub(ByteScale(300)).to_string(),
+            "-300 B"
+        );
+        assert_eq!(today.signed_sub(today).to_string(), "0 B");
+        assert_eq!(
+            ByteScale128(i128::MAX as u128)
+                .signed_sub(ByteScale128(0))
+                .as_i128(),
+            i128::MAX
+        );
+        assert!(ByteDelta::checked_between(0, u128::MAX).is_none());
+        assert_eq!(
+            ByteDelta::checked_between(i128::MIN.unsigned_abs(), 0),
+            Some(ByteDelta(i128::MIN))
+        );
+
+        let delta = ByteDelta(-1_500_000);
+        assert!(delta.is_negative());
+        assert_eq!(delta.magnitude(), 1_500_000);
+        assert_eq!(format!("{:#.2}", delta), "-1.50 MB");
+        assert_eq!(format!("{:>10}", delta), "  -1.4 MiB");
+        assert_eq!(delta.to_string_as(Format::SI), "-1.5 MB");
+        assert_eq!(-delta + ByteDelta(500), ByteDelta(1_500_500));
+        assert_eq!(ByteDelta(i128::MIN).checked_neg(), None);
+        assert_eq!(delta.checked_neg(), Some(ByteDelta(1_500_000)));
+        assert_eq!(ByteDelta(i128::MAX).checked_add(ByteDelta(1)), None);
+        assert_eq!(ByteDelta(i128::MIN).checked_sub(ByteDelta(1)), None);
+        assert_eq!(
+            delta.checked_sub(ByteDelta(500)),
+            Some(ByteDelta(-1_500_500))
+        );
+
+        assert_eq!("+1.5 KiB".parse::<ByteDelta>(), Ok(ByteDelta(1536)));
+        assert_eq!("-300 B".parse::<ByteDelta>(), Ok(ByteDelta(-300)));
+        assert_eq!("42".parse::<ByteDelta>(), Ok(ByteDelta(42)));
+        assert_eq!("-0".parse::<ByteDelta>(), Ok(ByteDelta(0)));
+        let error = "--1 KB".parse::<ByteDelta>().unwrap_err();
+        assert_eq!(
+            (error.kind(), error.span()),
+            (ParseErrorKind::InvalidNumber, 1..2)
+        );
+        let error = "-1 XB".parse::<ByteDelta>().unwrap_err();
+        assert_eq!(
+            (error.kind(), error.span()),
+            (ParseErrorKind::UnknownUnit, 3..5)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "byte delta overflows i128")]
+    fn test_signed_delta_overflow() {
+        let _ = ByteDelta(i128::MAX) + ByteDelta(1);
+    }
+
+    #[test]
+    fn test_rate() {
+        use core::time::Duration;
+
+        let rate = ByteScale::mib(124) / Duration::from_secs(10);
+        assert_eq!(rate.bytes_per_second(), 13_002_342);
+        assert_eq!(rate.to_string(), "12.4 MiB/s");
+        assert_eq!(format!("{:#}", rate), "13.0 MB/s");
+        assert_eq!(format!("{:.2}", rate), "12.40 MiB/s");
+        assert_eq!(
+            ByteScaleRate(12_400_000).to_bits_string_as(Format::SI),
+            "99.2 Mbit/s"
+        );
+        assert_eq!(
+            ByteScaleRate(100).to_bits_string_as(Format::SI),
+            "800 bit/s"
+        );
+        assert_eq!(ByteScaleRate(300).to_string(), "300 B/s");
+
+        assert_eq!(LinkSpeed(12_400_000).to_string(), "99.2 Mbit/s");
+        assert_eq!(format!("{:#}", LinkSpeed(12_400_000)), "94.6 Mibit/s");
+
+        assert_eq!(rate * Duration::from_secs(10), ByteScale(130_023_420));
+        assert_eq!(
+            ByteScaleRate::per_second(ByteScale::kib(1)) * Duration::from_millis(1500),
+            ByteScale(1536)
+        );
+        assert_eq!(
+            ByteScale::kib(3) / Duration::from_millis(1500),
+            ByteScaleRate(2048)
+        );
+
+        assert_eq!("12.4 MiB/s".parse(), Ok(ByteScaleRate(13_002_342)));
+        assert_eq!("99.2 Mbit/s".parse(), Ok(ByteScaleRate(12_400_000)));
+        assert_eq!("100 Mbps".parse(), Ok(ByteScaleRate(12_500_000)));
+        assert_eq!("100 Mb/s".parse(), Ok(ByteScaleRate(12_500_000)));
+        assert_eq!("100 MBps".parse(), Ok(ByteScaleRate(100_000_000)));
+        assert_eq!("1 Kibit/s".parse(), Ok(ByteScaleRate(128)));
+        assert_eq!("64 bit/s".parse(), Ok(ByteScaleRate(8)));
+        assert_eq!("512".parse(), Ok(ByteScaleRate(512)));
+        let error = "1 MiB/h".parse::<ByteScaleRate>().unwrap_err();
+        assert_eq!(
+            (error.kind(), error.span()),
+            (ParseErrorKind::TrailingGarbage, 5..7)
+        );
+        let error = "1 XB/s".parse::<ByteScaleRate>().unwrap_err();
+        assert_eq!(
+            (error.kind(), error.span()),
+            (ParseErrorKind::UnknownUnit, 2..4)
+        );
+    }
+
+    #[test]
+    fn test_rate_zero_duration() {
+        let result = std::panic::catch_unwind(|| ByteScale::kib(1) / core::time::Duration::ZERO);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_iterators() {
+        let files = [
+            ByteScale::kib(4),
+            ByteScale::mib(1),
+            ByteScale::kib(512),
+            ByteScale::b(100),
+        ];
+
+        assert_eq!(files.iter().sum::<ByteScale>(), ByteScale(1_577_060));
+        assert_eq!(files.into_iter().sum::<ByteScale>(), ByteScale(1_577_060));
+        assert_eq!(ByteScale::try_sum(files.iter()), Some(ByteScale(1_577_060)));
+        assert_eq!(
+            ByteScale::try_sum([ByteScale(u64::MAX), ByteScale(1)]),
+            None
+        );
+        assert_eq!(ByteScale::try_sum([ByteScale(0); 0]), Some(ByteScale(0)));
+
+        let stats = ByteScale::stats(files.iter()).unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.total, ByteScale(1_577_060));
+        assert_eq!(stats.min, ByteScale(100));
+        assert_eq!(stats.max, ByteScale::mib(1));
+        assert_eq!(stats.mean, ByteScale(394_265));
+        assert_eq!(stats.median, ByteScale::kib(258));
+
+        let odd = ByteScale::stats(files[..3].iter().copied()).unwrap();
+        assert_eq!(odd.median, ByteScale::kib(512));
+        assert_eq!(ByteScale::stats([ByteScale(0); 0]), None);
+        assert_eq!(ByteScale8::stats([ByteScale8(200), ByteScale8(100)]), None);
+    }
+
+    #[test]
+    fn test_checked_constructors() {
+        const TOO_LARGE: Option<ByteScale> = ByteScale::checked_pib(u64::MAX);
+        const QUOTA: Option<ByteScale> = ByteScale::checked_tib(4);
+        assert_eq!(TOO_LARGE, None);
+        assert_eq!(QUOTA, Some(ByteScale::gib(4096)));
+        assert_eq!(ByteScale8::checked_kb(1), None);
+        assert_eq!(ByteScale8::checked_kb(0), Some(ByteScale8(0)));
+    }
+
     #[test]
     fn test_comparison() {
         assert_eq!(ByteScale::mb(1), ByteScale::kb(1000));
@@ -105,6 +987,19 @@ mod tests {
         assert_eq!("|--357 B---|", format!("|{:-^10}|", ByteScale(357)));
     }
 
+    #[test]
+    fn test_display_flags() {
+        assert_eq!("2 GiB", format!("{:.0}", ByteScale::mib(1907)));
+        assert_eq!("1.862 GiB", format!("{:.3}", ByteScale::mib(1907)));
+        assert_eq!("2.0 GB", format!("{:#}", ByteScale::mib(1908)));
+        assert_eq!("1.99 GB", format!("{:#.2}", ByteScale::mib(1900)));
+        assert_eq!("357 B", format!("{:.3}", ByteScale(357)));
+
+        assert_eq!("|  1.86 GiB|", format!("|{:>10.2}|", ByteScale::mib(1907)));
+        assert_eq!("|1.9 GiB---|", format!("|{:-<10}|", ByteScale::mib(1907)));
+        assert_eq!("|-1.00 kB--|", format!("|{:-^#10.2}|", ByteScale::kb(1)));
+    }
+
     macro_rules! assert_to_string {
         ($expected:expr, $actual:expr, $si:expr) => {
             assert_eq!($expected.to_string(), $actual.to_string_as($si));
@@ -145,7 +1040,7 @@ mod tests {
     #[test]
     fn when_err() {
         // shortcut for writing test cases
-        fn parse(s: &str) -> Result<ByteScale, String> {
+        fn parse(s: &str) -> Result<ByteScale, ParseError> {
             s.parse::<ByteScale>()
         }
 
@@ -158,6 +1053,507 @@ mod tests {
         assert!(parse("1 000 B").is_err());
     }
 
+    #[test]
+    fn test_parse_error() {
+        fn error(s: &str) -> (ParseErrorKind, core::ops::Range<usize>) {
+            let error = s.parse::<ByteScale>().unwrap_err();
+            (error.kind(), error.span())
+        }
+
+        assert_eq!(error(""), (ParseErrorKind::Empty, 0..0));
+        assert_eq!(error("  "), (ParseErrorKind::Empty, 0..2));
+        assert_eq!(error("a124GB"), (ParseErrorKind::InvalidNumber, 0..6));
+        assert_eq!(error("1.5 XiB"), (ParseErrorKind::UnknownUnit, 4..7));
+        assert_eq!(error("20000 PiB"), (ParseErrorKind::Overflow, 0..9));
+        assert_eq!(error("-1.5 KiB"), (ParseErrorKind::Negative, 0..4));
+        assert_eq!(
+            error("1.3 42.0 B"),
+            (ParseErrorKind::TrailingGarbage, 4..10)
+        );
+        assert_eq!(
+            error("1 KiB extra"),
+            (ParseErrorKind::TrailingGarbage, 6..11)
+        );
+
+        assert_eq!(
+            "1.5 XiB".parse::<ByteScale>().unwrap_err().to_string(),
+            "unknown unit at bytes 4..7"
+        );
+        assert_eq!(
+            "xb".parse::<humanbyte::Unit>().err(),
+            Some(ParseError::new(ParseErrorKind::UnknownUnit, 0..2))
+        );
+    }
+
+    #[test]
+    fn test_parse_exact() {
+        fn parse(s: &str) -> Result<ByteScale, ParseError> {
+            s.parse::<ByteScale>()
+        }
+
+        assert_eq!(
+            parse("9007199254740993 B"),
+            Ok(ByteScale(9_007_199_254_740_993))
+        );
+        assert_eq!(
+            parse("16777216.000001 MiB"),
+            Ok(ByteScale(17_592_186_044_417))
+        );
+        assert_eq!(parse("1.9999 KiB"), Ok(ByteScale(2047)));
+        assert_eq!(parse("0.5KB"), Ok(ByteScale(500)));
+        assert_eq!(parse(".5 kb"), Ok(ByteScale(500)));
+        assert_eq!(parse("1.5"), Ok(ByteScale(1)));
+        assert_eq!(parse("+12"), Ok(ByteScale(12)));
+        assert_eq!(
+            parse("15.999999999999999999 PiB"),
+            Ok(ByteScale(18_014_398_509_481_983))
+        );
+        assert_eq!(parse("18446744073709551615"), Ok(ByteScale(u64::MAX)));
+        assert!(parse("18446744073709551616").is_err());
+        assert!(parse("16384 PiB").is_err());
+        assert!(parse("100000000000000000000000000000000000000000 B").is_err());
+        assert!(parse(".").is_err());
+    }
+
+    const PARSE_UNITS: [(&str, u64); 13] = [
+        ("B", humanbyte::B),
+        ("KB", humanbyte::KB),
+        ("KiB", humanbyte::KIB),
+        ("MB", humanbyte::MB),
+        ("MiB", humanbyte::MIB),
+        ("GB", humanbyte::GB),
+        ("GiB", humanbyte::GIB),
+        ("TB", humanbyte::TB),
+        ("TiB", humanbyte::TIB),
+        ("PB", humanbyte::PB),
+        ("PiB", humanbyte::PIB),
+        ("EB", humanbyte::EB),
+        ("EiB", humanbyte::EIB),
+    ];
+
+    proptest::proptest! {
+        #[test]
+        fn test_parse_matches_big_integer(
+            integer in proptest::prop_oneof![0u64..1_000_000, proptest::num::u64::ANY],
+            fraction in "[0-9]{0,30}",
+            unit in 0..PARSE_UNITS.len(),
+        ) {
+            use num_bigint::BigUint;
+
+            let (suffix, multiplier) = PARSE_UNITS[unit];
+            let input = if fraction.is_empty() {
+                format!("{} {}", integer, suffix)
+            } else {
+                format!("{}.{} {}", integer, fraction, suffix)
+            };
+
+            let scale = BigUint::from(10u32).pow(fraction.len() as u32);
+            let fraction = fraction.parse::<BigUint>().unwrap_or_default();
+            let expected = (BigUint::from(integer) * &scale + fraction) * multiplier / scale;
+            match u64::try_from(expected) {
+                Ok(expected) => proptest::prop_assert_eq!(input.parse::<ByteScale>(), Ok(ByteScale(expected))),
+                Err(_) => proptest::prop_assert!(input.parse::<ByteScale>().is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_exa_and_larger_units() {
+        assert_eq!(ByteScale::eb(2).as_u64(), 2 * humanbyte::EB);
+        assert_eq!(ByteScale::eib(15).as_u64(), 15 << 60);
+        assert_display!("16.0 EiB", ByteScale(u64::MAX));
+        assert_eq!(
+            "16.0 EiB".parse::<ByteScale>().unwrap_err().kind(),
+            ParseErrorKind::Overflow
+        );
+        assert_eq!(
+            "15.5 EiB".parse::<ByteScale>().unwrap(),
+            ByteScale(31 << 59)
+        );
+        assert_eq!("1 EB".parse::<ByteScale>().unwrap(), ByteScale::eb(1));
+        assert_eq!(
+            "1 ZiB".parse::<ByteScale>().unwrap_err().kind(),
+            ParseErrorKind::Overflow
+        );
+
+        assert_eq!(ByteScale128::zib(1).as_u128(), 1 << 70);
+        assert_eq!(ByteScale128::qb(3).as_u128(), 3 * humanbyte::QB);
+        assert_display!("1.0 YiB", ByteScale128::yib(1));
+        assert_display!("268435456.0 QiB", ByteScale128(u128::MAX));
+        assert_eq!(ByteScale128::rb(2).to_string_as(Format::SI), "2.0 RB");
+        assert_eq!(
+            "2.5 ZB".parse::<ByteScale128>().unwrap(),
+            ByteScale128::eb(2500)
+        );
+        assert_eq!(
+            "1 QiB".parse::<ByteScale128>().unwrap(),
+            ByteScale128::qib(1)
+        );
+        assert_eq!(u64::try_from(humanbyte::Unit::ExbiByte), Ok(humanbyte::EIB));
+        assert!(u64::try_from(humanbyte::Unit::ZettaByte).is_err());
+    }
+
+    #[test]
+    fn test_to_string_exact() {
+        assert_eq!(ByteScale(0).to_string_exact(), "0 B");
+        assert_eq!(ByteScale(1536).to_string_exact(), "1536 B");
+        assert_eq!(ByteScale::mib(3).to_string_exact(), "3 MiB");
+        assert_eq!(ByteScale::kib(1025).to_string_exact(), "1025 KiB");
+        assert_eq!(ByteScale::eib(16 - 1).to_string_exact(), "15 EiB");
+        assert_eq!(
+            ByteScale(u64::MAX).to_string_exact(),
+            "18446744073709551615 B"
+        );
+        assert_eq!(
+            humanbyte::to_string_exact(ByteScale::mb(1907).as_u64(), Format::SI),
+            "1907 MB"
+        );
+        assert_eq!(Dashboard::kib(1).to_string_exact(), "1024 B");
+        assert_eq!(ByteScale128::qib(1 << 20).to_string_exact(), "1048576 QiB");
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_parse_bytes_matches_parse(
+            input in "[+-]?[0-9]{0,22}(\\.[0-9]{0,4})? ?(k|Ki|MB|mib|Gbit|kibibytes|byte|XB)?",
+        ) {
+            let expected = input.parse::<ByteScale>().map(|size| size.as_u64());
+            proptest::prop_assert_eq!(
+                humanbyte::parse_bytes(&input),
+                expected.map_err(|error| error.kind())
+            );
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_to_string_exact_round_trip(bytes in proptest::num::u64::ANY, shift in 0..64u32) {
+            // shifting produces values with many trailing zero bits, which use the larger units
+            let size = ByteScale(bytes << shift);
+            proptest::prop_assert_eq!(size.to_string_exact().parse::<ByteScale>(), Ok(size));
+            let si = humanbyte::to_string_exact(size.as_u64(), Format::SI);
+            proptest::prop_assert_eq!(si.parse::<ByteScale>(), Ok(size));
+        }
+    }
+
+    #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Default, HumanByte)]
+    struct ByteScale8(u8);
+
+    #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Default, HumanByte)]
+    struct ByteScale32(u32);
+
+    #[derive(HumanByteRate)]
+    #[humanbyte(size = "ByteScale32", format = "si-bits")]
+    struct LinkSpeed(u64);
+
+    #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Default, HumanByte)]
+    struct ByteScale128(u128);
+
+    #[test]
+    fn test_inner_types() {
+        assert_eq!(ByteScale8::b(255).as_u8(), 255);
+        assert_eq!(ByteScale32::gib(3).as_u32(), 3 * 1024 * 1024 * 1024);
+        assert_eq!(ByteScale32::kb(1).as_u64(), 1_000);
+        assert_eq!(ByteScale128::pib(1 << 20).as_u128(), 1 << 70);
+
+        assert_eq!((ByteScale32::kib(1) + 24u16).as_u32(), 1_048);
+        assert_eq!((2u8 * ByteScale32::kib(1)).as_u32(), 2_048);
+
+        assert_display!("255 B", ByteScale8(255));
+        assert_display!("3.0 GiB", ByteScale32::gib(3));
+        assert_display!("1.0 ZiB", ByteScale128::pib(1 << 20));
+
+        assert_eq!("255 B".parse::<ByteScale8>().unwrap(), ByteScale8(255));
+        assert!("256 B".parse::<ByteScale8>().is_err());
+        assert!("1 KiB".parse::<ByteScale8>().is_err());
+        assert_eq!(
+            "4 GiB".parse::<ByteScale128>().unwrap(),
+            ByteScale128(1 << 32)
+        );
+        assert!("4 GiB".parse::<ByteScale32>().is_err());
+    }
+
+    #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Default, HumanByte)]
+    struct Named {
+        bytes: u64,
+    }
+
+    #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord)]
+    struct Disk;
+
+    #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, HumanByte)]
+    struct Tagged<T: Copy>(u64, core::marker::PhantomData<T>);
+
+    #[derive(Clone, PartialEq, Eq, HumanByte)]
+    struct Labelled {
+        label: &'static str,
+        #[humanbyte(bytes)]
+        size: u32,
+    }
+
+    #[test]
+    fn test_struct_shapes() {
+        assert_eq!(Named::kib(2).as_u64(), 2048);
+        assert_eq!((Named::kib(2) + Named::b(1)).bytes, 2049);
+        assert_display!("2.0 KiB", Named::kib(2));
+        assert_eq!("2 KiB".parse::<Named>().unwrap(), Named { bytes: 2048 });
+
+        let disk = Tagged::<Disk>::mib(1) - 24u32;
+        assert_eq!(disk.as_u64(), 1_048_552);
+        assert_display!("1.0 MiB", 2u8 * Tagged::<Disk>::kib(512));
+        assert!(Tagged::<Disk>::range_start(0u64).contains(&disk));
+
+        let labelled = Labelled::kb(3);
+        assert_eq!(labelled.label, "");
+        assert_eq!(labelled.as_u32(), 3_000);
+        assert_display!("2.9 KiB", labelled);
+
+        // operators carry other fields over from the consumed operand
+        let labelled = Labelled {
+            label: "x",
+            size: 1,
+        };
+        assert_eq!((labelled.clone() + 1u32).label, "x");
+        assert_eq!((2u8 * labelled.clone()).label, "x");
+        assert_eq!(labelled.clone().saturating_sub(Labelled::b(5)).label, "x");
+        assert_eq!((labelled % 2u32).label, "x");
+    }
+
+    #[test]
+    fn test_range() {
+        use core::ops::{Bound, RangeBounds};
+        use humanbyte::HumanByteRange;
+
+        let bucket = ByteScale::range_until(ByteScale::mib(1), ByteScale::gib(4));
+        assert!(bucket.contains(&ByteScale::mib(1)));
+        assert!(!bucket.contains(&ByteScale::gib(4)));
+        assert_eq!(bucket.end_bound(), Bound::Excluded(&ByteScale::gib(4)));
+        assert_eq!(bucket.len(), Some(ByteScale::gib(4) - ByteScale::mib(1)));
+
+        assert_eq!(bucket.to_string(), "1.0 MiB..4.0 GiB");
+        let inclusive = ByteScale::range(ByteScale::mib(1), ByteScale::gib(4));
+        assert_eq!(format!("{:.0}", inclusive), "1 MiB..=4 GiB");
+        assert_eq!(format!("{:#}", ByteScale::range_stop(KB)), "..=1.0 kB");
+        assert_eq!(ByteScale::range_start(0u64).to_string(), "0 B..");
+        assert_eq!(HumanByteRange::<ByteScale>::from(..).to_string(), "..");
+
+        // unbounded ends cover the whole inner type
+        assert!(ByteScale::range_start(0u64).contains(&ByteScale(u64::MAX)));
+        assert_eq!(ByteScale::range_start(0u64).len(), None);
+        assert_eq!(
+            ByteScale::range_start(1u64).len(),
+            Some(ByteScale(u64::MAX))
+        );
+
+        assert!(HumanByteRange::from(ByteScale::kib(1)..ByteScale::kib(1)).is_empty());
+        assert!(ByteScale::range(2 * KB, KB).is_empty());
+        assert_eq!(ByteScale::range(2 * KB, KB).len(), Some(ByteScale(0)));
+        let after_max =
+            HumanByteRange::from_bounds(Bound::Excluded(ByteScale(u64::MAX)), Bound::Unbounded);
+        assert!(after_max.is_empty());
+        // an exclusive start is the next size
+        let above = HumanByteRange::from_bounds(Bound::Excluded(ByteScale(1023)), Bound::Unbounded);
+        assert_eq!(above, ByteScale::range_start(1024u64));
+
+        let small = HumanByteRange::from(..ByteScale::kib(1));
+        let medium = ByteScale::range_until(ByteScale::kib(1), ByteScale::mib(1));
+        let overlap = ByteScale::range(ByteScale::kib(512), ByteScale::mib(2));
+        assert!(small.intersection(&medium).is_empty());
+        assert_eq!(
+            medium.intersection(&overlap).to_string(),
+            "512.0 KiB..1.0 MiB"
+        );
+        assert_eq!(small.union(&medium).unwrap().to_string(), "..1.0 MiB");
+        assert_eq!(
+            ByteScale::range_stop(1023u64).union(&medium),
+            Some(HumanByteRange::from(..ByteScale::mib(1)))
+        );
+        assert_eq!(
+            medium.union(&overlap),
+            Some(ByteScale::range(ByteScale::kib(1), ByteScale::mib(2)))
+        );
+        assert_eq!(
+            small.union(&ByteScale::range_start(ByteScale::kib(2))),
+            None
+        );
+        assert_eq!(after_max.union(&small), Some(small));
+    }
+
+    #[test]
+    fn test_range_iterators() {
+        use humanbyte::HumanByteRange;
+
+        let parts: Vec<_> = ByteScale::mib(10).chunks(ByteScale::mib(4)).collect();
+        assert_eq!(
+            parts,
+            [
+                (ByteScale(0), ByteScale::mib(4)),
+                (ByteScale::mib(4), ByteScale::mib(4)),
+                (ByteScale::mib(8), ByteScale::mib(2)),
+            ]
+        );
+        let chunks = ByteScale::gib(10).chunks(ByteScale::mib(64));
+        assert_eq!(chunks.size_hint(), (160, Some(160)));
+        assert_eq!(
+            chunks.last(),
+            Some((ByteScale::mib(10176), ByteScale::mib(64)))
+        );
+        assert_eq!(ByteScale(0).chunks(ByteScale::mib(4)).count(), 0);
+        // an unbounded end runs up to the largest size
+        let (offset, len) = ByteScale::range_start(u64::MAX - 1)
+            .chunks(ByteScale::kib(1))
+            .last()
+            .unwrap();
+        assert_eq!((offset, len), (ByteScale(u64::MAX - 1), ByteScale(2)));
+
+        let steps: Vec<_> = ByteScale::range(ByteScale::mib(1), ByteScale::mib(3))
+            .step_by(ByteScale::mib(1))
+            .collect();
+        assert_eq!(
+            steps,
+            [ByteScale::mib(1), ByteScale::mib(2), ByteScale::mib(3)]
+        );
+        let steps =
+            HumanByteRange::from(ByteScale::kib(1)..ByteScale::kib(3)).step_by(ByteScale::kib(1));
+        assert_eq!(steps.size_hint(), (2, Some(2)));
+        assert_eq!(steps.count(), 2);
+        assert_eq!(
+            ByteScale::range_start(u64::MAX)
+                .step_by(ByteScale(2))
+                .collect::<Vec<_>>(),
+            [ByteScale(u64::MAX)]
+        );
+
+        let pieces: Vec<_> = ByteScale(10).split_into(3).collect();
+        assert_eq!(
+            pieces,
+            [
+                (ByteScale(0), ByteScale(4)),
+                (ByteScale(4), ByteScale(3)),
+                (ByteScale(7), ByteScale(3)),
+            ]
+        );
+        assert_eq!(ByteScale(2).split_into(5).len(), 2);
+        assert_eq!(ByteScale(0).split_into(5).len(), 0);
+        // all sizes of the inner type, whose length does not fit into it
+        let halves: Vec<_> = HumanByteRange::<ByteScale>::from(..)
+            .split_into(2)
+            .unwrap()
+            .collect();
+        assert_eq!(
+            halves,
+            [
+                (ByteScale(0), ByteScale(1 << 63)),
+                (ByteScale(1 << 63), ByteScale(1 << 63)),
+            ]
+        );
+        let all: Vec<_> = HumanByteRange::<ByteScale128>::from(..)
+            .split_into(3)
+            .unwrap()
+            .collect();
+        let (offset, len) = all[2];
+        assert_eq!(all.len(), 3);
+        assert_eq!(offset.0 + (len.0 - 1), u128::MAX);
+        // a single piece of all sizes is one more than the largest size
+        assert!(HumanByteRange::<ByteScale>::from(..)
+            .split_into(1)
+            .is_none());
+        assert!(HumanByteRange::<ByteScale128>::from(..)
+            .split_into(1)
+            .is_none());
+        let nearly_all = HumanByteRange::from(ByteScale(1)..);
+        assert_eq!(
+            nearly_all.split_into(1).unwrap().collect::<Vec<_>>(),
+            [(ByteScale(1), ByteScale(u64::MAX))]
+        );
+        assert_eq!(
+            ByteScale(u64::MAX).split_into(1).collect::<Vec<_>>(),
+            [(ByteScale(0), ByteScale(u64::MAX))]
+        );
+    }
+
+    #[test]
+    fn test_range_iterators_size_hint_full_u128() {
+        use humanbyte::HumanByteRange;
+
+        // one item per size of all `u128` sizes is one more than `u128::MAX`
+        let all = HumanByteRange::<ByteScale128>::from(..);
+        assert_eq!(all.chunks(ByteScale128(1)).size_hint(), (usize::MAX, None));
+        assert_eq!(all.step_by(ByteScale128(1)).size_hint(), (usize::MAX, None));
+        assert_eq!(
+            all.step_by(ByteScale128(1 << 127)).size_hint(),
+            (2, Some(2))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_chunks_zero() {
+        ByteScale::kib(1).chunks(0u64);
+    }
+
+    #[test]
+    fn test_range_parse() {
+        use humanbyte::{HumanByteRange, ParseErrorKind};
+
+        let parse = |value: &str| ByteScale::parse_range(value).unwrap();
+        assert_eq!(
+            parse("10MiB..1GiB"),
+            ByteScale::range_until(ByteScale::mib(10), ByteScale::gib(1))
+        );
+        assert_eq!(
+            parse(" 1 KiB ..= 4 KiB "),
+            ByteScale::range(ByteScale::kib(1), ByteScale::kib(4))
+        );
+        assert_eq!(parse("500M.."), ByteScale::range_start(ByteScale::mb(500)));
+        assert_eq!(parse("..4k"), HumanByteRange::from(..ByteScale::kb(4)));
+        assert_eq!(parse("..=4k"), ByteScale::range_stop(ByteScale::kb(4)));
+        assert_eq!(parse(".."), HumanByteRange::from(..));
+        assert_eq!(parse(">=500M"), ByteScale::range_start(ByteScale::mb(500)));
+        assert_eq!(parse("> 1 KiB"), ByteScale::range_start(1025u64));
+        assert_eq!(parse("<=4k"), ByteScale::range_stop(ByteScale::kb(4)));
+        assert_eq!(parse("<4k"), HumanByteRange::from(..ByteScale::kb(4)));
+        // fractions parse as sizes, not as range dots
+        assert_eq!(
+            parse("0.5 KiB..1.5 KiB"),
+            ByteScale::range_until(512u64, 1536u64)
+        );
+
+        // display round-trips at full precision
+        let range = ByteScale::range(ByteScale::kib(1), ByteScale::mib(4));
+        assert_eq!(parse(&range.to_string()), range);
+
+        let error = |value: &str| {
+            let error = ByteScale::parse_range(value).unwrap_err();
+            (error.kind(), error.span())
+        };
+        assert_eq!(error(" "), (ParseErrorKind::Empty, 0..1));
+        assert_eq!(error("4k"), (ParseErrorKind::InvalidRange, 0..2));
+        assert_eq!(error("1k..="), (ParseErrorKind::InvalidRange, 2..5));
+        assert_eq!(error("1k.. 2 XB"), (ParseErrorKind::UnknownUnit, 7..9));
+        assert_eq!(error(">= 1 XB"), (ParseErrorKind::UnknownUnit, 5..7));
+        assert_eq!(error("1k..2k..3k").0, ParseErrorKind::TrailingGarbage);
+    }
+
+    #[derive(Copy, Clone, PartialEq, Eq, HumanByte)]
+    #[humanbyte(format = "si", precision = 2)]
+    struct Dashboard(u64);
+
+    #[test]
+    fn test_container_attributes() {
+        assert_display!("215 B", Dashboard::b(215));
+        assert_display!("1.02 kB", Dashboard::kib(1));
+        assert_display!("2.00 GB", Dashboard::mib(1908));
+        assert_eq!("1.02 kB", format!("{:?}", Dashboard::kib(1)));
+        assert_eq!("1.00 KiB", Dashboard::kib(1).to_string_as(Format::IEC));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow constructing `ByteScale` from pebibytes")]
+    fn test_constructor_overflow() {
+        ByteScale::pib(u64::MAX);
+    }
+
     #[test]
     fn test_default() {
         assert_eq!(ByteScale::b(0), ByteScale::default());
@@ -192,5 +1588,89 @@ mod tests {
         // i64 MAX
         let s: S = toml::from_str(r#"x = "9223372036854775807""#).unwrap();
         assert_eq!(s.x, "9223372036854775807".parse::<ByteScale>().unwrap());
+
+        #[derive(Serialize)]
+        struct D {
+            x: Dashboard,
+        }
+
+        let d = D {
+            x: Dashboard::kb(1),
+        };
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#"{"x":"1.00 kB"}"#);
+
+        #[derive(PartialEq, HumanByte)]
+        #[humanbyte(serialize = "exact")]
+        struct Exact(u64);
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct E {
+            x: Exact,
+        }
+
+        let e = E {
+            x: Exact::mib(1907),
+        };
+        let json = serde_json::to_string(&e).unwrap();
+        assert_eq!(json, r#"{"x":"1907 MiB"}"#);
+        assert_eq!(serde_json::from_str::<E>(&json).unwrap(), e);
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct R {
+            x: ByteScaleRate,
+        }
+
+        let r = R {
+            x: ByteScaleRate(12_400_000),
+        };
+        assert_eq!(serde_json::to_string(&r).unwrap(), r#"{"x":"11.8 MiB/s"}"#);
+        let r: R = serde_json::from_str(r#"{"x":"99.2 Mbit/s"}"#).unwrap();
+        assert_eq!(r.x, ByteScaleRate(12_400_000));
+        let r: R = serde_json::from_str(r#"{"x":1024}"#).unwrap();
+        assert_eq!(r.x, ByteScaleRate(1024));
+
+        #[derive(PartialEq, HumanByte)]
+        #[humanbyte(convention = "kubernetes")]
+        struct Quantity(u64);
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Limits {
+            memory: Quantity,
+            requests: humanbyte::HumanByteRange<Quantity>,
+        }
+
+        // rounded and exact sizes written by the Kubernetes type read back in its convention
+        let limits = Limits {
+            memory: Quantity::mib(64),
+            requests: Quantity::range_until(Quantity::b(100), Quantity::mib(2)),
+        };
+        let json = serde_json::to_string(&limits).unwrap();
+        assert_eq!(json, r#"{"memory":"64.0 MiB","requests":"100 B..2 MiB"}"#);
+        assert_eq!(serde_json::from_str::<Limits>(&json).unwrap(), limits);
+        let limits: Limits = toml::from_str("memory = \"64Mi\"\nrequests = \">=1.5M\"").unwrap();
+        assert_eq!(limits.memory, Quantity::mib(64));
+        assert_eq!(limits.requests, Quantity::range_start(Quantity::kb(1500)));
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Filter {
+            size: humanbyte::HumanByteRange<ByteScale>,
+        }
+
+        let filter: Filter = toml::from_str(r#"size = "10 MiB..1 GiB""#).unwrap();
+        assert_eq!(
+            filter.size,
+            ByteScale::range_until(ByteScale::mib(10), ByteScale::gib(1))
+        );
+        let filter: Filter = toml::from_str(r#"size = ">=500M""#).unwrap();
+        assert_eq!(filter.size, ByteScale::range_start(ByteScale::mb(500)));
+        assert!(toml::from_str::<Filter>(r#"size = "4k""#).is_err());
+
+        // serialized exactly, so that odd sizes survive a round trip
+        let filter = Filter {
+            size: ByteScale::range(ByteScale::mib(1), ByteScale(4_000_000_001)),
+        };
+        let json = serde_json::to_string(&filter).unwrap();
+        assert_eq!(json, r#"{"size":"1 MiB..=4000000001 B"}"#);
+        assert_eq!(serde_json::from_str::<Filter>(&json).unwrap(), filter);
     }
 }
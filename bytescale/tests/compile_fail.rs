@@ -0,0 +1,6 @@
+//! Checks that invalid `bytes!` literals and derive inputs fail to compile with the expected errors.
+
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,5 @@
+use bytescale::bytes;
+
+const LIMIT: u64 = bytes!("4.5 MiB/s");
+
+fn main() {}
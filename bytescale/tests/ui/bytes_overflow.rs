@@ -0,0 +1,6 @@
+// neither allowing nor capping lints lets an overflowing size truncate
+#![allow(overflowing_literals)]
+
+const WORD: u32 = bytescale::bytes!("8 GiB");
+
+fn main() {}
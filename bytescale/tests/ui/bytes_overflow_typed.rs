@@ -0,0 +1,10 @@
+#![allow(overflowing_literals)]
+
+use bytescale::{bytes, HumanByte};
+
+#[derive(HumanByte)]
+struct Small(u8);
+
+const LIMIT: Small = bytes!(Small, "1 KiB");
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bytescale::HumanByte;
+
+#[derive(HumanByte)]
+enum Size {
+    Bytes(u64),
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use bytescale::HumanByte;
+
+#[derive(HumanByte)]
+struct Size(i64);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use bytescale::HumanByte;
+
+#[derive(HumanByte)]
+struct Size;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use bytescale::HumanByte;
+
+#[derive(HumanByte)]
+struct Size {
+    bytes: u64,
+    limit: u64,
+}
+
+fn main() {}
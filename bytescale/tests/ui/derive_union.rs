@@ -0,0 +1,8 @@
+use bytescale::HumanByte;
+
+#[derive(HumanByte)]
+union Size {
+    bytes: u64,
+}
+
+fn main() {}
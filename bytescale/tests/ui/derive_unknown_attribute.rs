@@ -0,0 +1,7 @@
+use bytescale::HumanByte;
+
+#[derive(HumanByte)]
+#[humanbyte(units = "iec")]
+struct Size(u64);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use bytescale::HumanByte;
+
+#[derive(HumanByte)]
+struct Size {
+    #[humanbyte(size)]
+    bytes: u64,
+    limit: u64,
+}
+
+fn main() {}
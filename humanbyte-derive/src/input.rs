@@ -0,0 +1,399 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_quote, DeriveInput};
+
+/// Unsigned integer types accepted as the byte-carrying field of a `HumanByte` new type.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Inner {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl Inner {
+    fn from_type(ty: &syn::Type) -> Option<Self> {
+        let syn::Type::Path(path) = ty else {
+            return None;
+        };
+        if path.qself.is_some() {
+            return None;
+        }
+        match path.path.segments.last()?.ident.to_string().as_str() {
+            "u8" => Some(Inner::U8),
+            "u16" => Some(Inner::U16),
+            "u32" => Some(Inner::U32),
+            "u64" => Some(Inner::U64),
+            "u128" => Some(Inner::U128),
+            "usize" => Some(Inner::Usize),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Inner::U8 => "u8",
+            Inner::U16 => "u16",
+            Inner::U32 => "u32",
+            Inner::U64 => "u64",
+            Inner::U128 => "u128",
+            Inner::Usize => "usize",
+        }
+    }
+
+    pub(crate) fn ident(self) -> syn::Ident {
+        syn::Ident::new(self.name(), Span::call_site())
+    }
+
+    /// Primitive types which convert into this one without loss via `From`.
+    pub(crate) fn lossless_sources(self) -> &'static [Inner] {
+        match self {
+            Inner::U8 => &[Inner::U8],
+            Inner::U16 => &[Inner::U16, Inner::U8],
+            Inner::U32 => &[Inner::U32, Inner::U16, Inner::U8],
+            Inner::U64 => &[Inner::U64, Inner::U32, Inner::U16, Inner::U8],
+            Inner::U128 => &[Inner::U128, Inner::U64, Inner::U32, Inner::U16, Inner::U8],
+            Inner::Usize => &[Inner::Usize, Inner::U16, Inner::U8],
+        }
+    }
+
+    /// Whether the type always fits into a `u64`.
+    pub(crate) fn fits_u64(self) -> bool {
+        matches!(self, Inner::U8 | Inner::U16 | Inner::U32 | Inner::U64)
+    }
+}
+
+/// The default display format chosen with `#[humanbyte(format = "...")]`.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum Format {
+    #[default]
+    Iec,
+    Si,
+    IecBits,
+    SiBits,
+    IecLong,
+    SiLong,
+}
+
+impl Format {
+    /// Returns the matching `humanbyte::Format` variant.
+    pub(crate) fn to_tokens(self) -> TokenStream2 {
+        match self {
+            Format::Iec => quote!(::humanbyte::Format::IEC),
+            Format::Si => quote!(::humanbyte::Format::SI),
+            Format::IecBits => quote!(::humanbyte::Format::IECBits),
+            Format::SiBits => quote!(::humanbyte::Format::SIBits),
+            Format::IecLong => quote!(::humanbyte::Format::IECLong),
+            Format::SiLong => quote!(::humanbyte::Format::SILong),
+        }
+    }
+
+    /// The format selected by the `{:#}` flag, which switches between binary and decimal prefixes.
+    pub(crate) fn alternate(self) -> Self {
+        match self {
+            Format::Iec => Format::Si,
+            Format::Si => Format::Iec,
+            Format::IecBits => Format::SiBits,
+            Format::SiBits => Format::IecBits,
+            Format::IecLong => Format::SiLong,
+            Format::SiLong => Format::IecLong,
+        }
+    }
+}
+
+/// The parse convention chosen with `#[humanbyte(convention = "...")]`.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum Convention {
+    #[default]
+    Standard,
+    Jedec,
+    Kubernetes,
+    Gnu,
+}
+
+impl Convention {
+    /// Returns the matching `humanbyte::Convention` variant.
+    pub(crate) fn to_tokens(self) -> TokenStream2 {
+        match self {
+            Convention::Standard => quote!(::humanbyte::Convention::Standard),
+            Convention::Jedec => quote!(::humanbyte::Convention::Jedec),
+            Convention::Kubernetes => quote!(::humanbyte::Convention::Kubernetes),
+            Convention::Gnu => quote!(::humanbyte::Convention::Gnu),
+        }
+    }
+}
+
+/// A field other than the byte-carrying one, filled in whenever the new type is constructed.
+struct OtherField {
+    member: syn::Member,
+    phantom: bool,
+}
+
+/// The parts of the derive input shared by every `HumanByte` generator.
+pub(crate) struct HumanByteInput {
+    pub(crate) name: syn::Ident,
+    pub(crate) generics: syn::Generics,
+    /// The byte-carrying field, i.e. `0` for tuple structs or the field name otherwise.
+    pub(crate) member: syn::Member,
+    pub(crate) inner: Inner,
+    others: Vec<OtherField>,
+    /// Format used by `Display`, set with
+    /// `#[humanbyte(format = "iec" | "si" | "iec-bits" | "si-bits" | "iec-long" | "si-long")]`.
+    pub(crate) format: Format,
+    /// Decimal places used by `Display`, set with `#[humanbyte(precision = N)]`.
+    pub(crate) precision: usize,
+    /// Whether human-readable serialization uses the lossless `to_string_exact` representation,
+    /// set with `#[humanbyte(serialize = "display" | "exact")]`.
+    pub(crate) serialize_exact: bool,
+    /// Convention used by `FromStr` and `parse_strict`, set with
+    /// `#[humanbyte(convention = "standard" | "jedec" | "kubernetes" | "gnu")]`.
+    pub(crate) convention: Convention,
+    /// The size type a rate is measured in, set with `#[humanbyte(size = "Type")]`.
+    pub(crate) size: Option<syn::Type>,
+}
+
+impl HumanByteInput {
+    pub(crate) fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let syn::Data::Struct(data) = &input.data else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "HumanByte derives only support structs",
+            ));
+        };
+
+        let mut marked = Vec::new();
+        for (index, field) in data.fields.iter().enumerate() {
+            if has_bytes_marker(field)? {
+                marked.push(index);
+            }
+        }
+        let candidates: Vec<usize> = if marked.is_empty() {
+            data.fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| !is_phantom_data(&field.ty))
+                .map(|(index, _)| index)
+                .collect()
+        } else {
+            marked
+        };
+        let message = match candidates.as_slice() {
+            [_] => None,
+            [] => Some(
+                "HumanByte derives require a field of type u8, u16, u32, u64, u128 or usize \
+                 carrying the number of bytes",
+            ),
+            _ => Some(
+                "HumanByte derives require exactly one byte-carrying field; mark it with \
+                 `#[humanbyte(bytes)]`",
+            ),
+        };
+        if let Some(message) = message {
+            return Err(syn::Error::new_spanned(&input.ident, message));
+        }
+        let index = candidates[0];
+
+        let mut member = None;
+        let mut inner = None;
+        let mut others = Vec::new();
+        for (field_index, field) in data.fields.iter().enumerate() {
+            let field_member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(field_index.into()),
+            };
+            if field_index == index {
+                inner = Some(Inner::from_type(&field.ty).ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &field.ty,
+                        "HumanByte derives support u8, u16, u32, u64, u128 and usize fields",
+                    )
+                })?);
+                member = Some(field_member);
+            } else {
+                others.push(OtherField {
+                    member: field_member,
+                    phantom: is_phantom_data(&field.ty),
+                });
+            }
+        }
+
+        let mut format = Format::default();
+        let mut precision = 1;
+        let mut serialize_exact = false;
+        let mut size = None;
+        let mut convention = Convention::default();
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("humanbyte"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("format") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    format = match value.value().to_lowercase().as_str() {
+                        "iec" => Format::Iec,
+                        "si" => Format::Si,
+                        "iec-bits" => Format::IecBits,
+                        "si-bits" => Format::SiBits,
+                        "iec-long" => Format::IecLong,
+                        "si-long" => Format::SiLong,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "unsupported format, expected \"iec\", \"si\", \"iec-bits\", \
+                                 \"si-bits\", \"iec-long\" or \"si-long\"",
+                            ))
+                        }
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("precision") {
+                    let value: syn::LitInt = meta.value()?.parse()?;
+                    precision = value.base10_parse()?;
+                    Ok(())
+                } else if meta.path.is_ident("serialize") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    serialize_exact = match value.value().as_str() {
+                        "display" => false,
+                        "exact" => true,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "unsupported serialization, expected \"display\" or \"exact\"",
+                            ))
+                        }
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("convention") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    convention = match value.value().to_lowercase().as_str() {
+                        "standard" => Convention::Standard,
+                        "jedec" => Convention::Jedec,
+                        "kubernetes" => Convention::Kubernetes,
+                        "gnu" => Convention::Gnu,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "unsupported convention, expected \"standard\", \"jedec\", \
+                                 \"kubernetes\" or \"gnu\"",
+                            ))
+                        }
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("size") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    size = Some(value.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unsupported humanbyte attribute, expected `format`, `precision`, \
+                         `serialize`, `convention` or `size`",
+                    ))
+                }
+            })?;
+        }
+
+        Ok(HumanByteInput {
+            name: input.ident.clone(),
+            generics: input.generics.clone(),
+            member: member.unwrap(),
+            inner: inner.unwrap(),
+            others,
+            format,
+            precision,
+            serialize_exact,
+            convention,
+            size,
+        })
+    }
+
+    /// Returns an expression constructing the new type around `bytes`.
+    ///
+    /// Other fields are filled with `PhantomData` or their `Default` value.
+    pub(crate) fn construct(&self, bytes: TokenStream2) -> TokenStream2 {
+        self.construct_with(bytes, |_| quote!(::core::default::Default::default()))
+    }
+
+    /// Returns an expression constructing the new type around `bytes`, moving other fields out of
+    /// `source`, an operand of type `Self` which is consumed, e.g. `self` in `self + rhs`.
+    pub(crate) fn construct_from(&self, source: TokenStream2, bytes: TokenStream2) -> TokenStream2 {
+        self.construct_with(bytes, |member| quote!(#source.#member))
+    }
+
+    fn construct_with(
+        &self,
+        bytes: TokenStream2,
+        other: impl Fn(&syn::Member) -> TokenStream2,
+    ) -> TokenStream2 {
+        let name = &self.name;
+        let member = &self.member;
+        let others = self.others.iter().map(|field| {
+            let member = &field.member;
+            if field.phantom {
+                quote!(#member: ::core::marker::PhantomData)
+            } else {
+                let value = other(member);
+                quote!(#member: #value)
+            }
+        });
+        quote!(#name { #member: #bytes, #(#others,)* })
+    }
+
+    /// Constructors can only be `const` when every other field is `PhantomData`.
+    pub(crate) fn constness(&self) -> Option<syn::Token![const]> {
+        if self.others.iter().all(|field| field.phantom) {
+            Some(Default::default())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the type generics extended with `param`, for impls that introduce their own generic
+    /// parameter.
+    pub(crate) fn generics_with(&self, param: syn::GenericParam) -> syn::Generics {
+        let mut generics = self.generics.clone();
+        match param {
+            syn::GenericParam::Lifetime(_) => generics.params.insert(0, param),
+            _ => generics.params.push(param),
+        }
+        generics
+    }
+
+    /// Returns `Self` as a type, including its generics.
+    pub(crate) fn ty(&self) -> syn::Type {
+        let name = &self.name;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        parse_quote!(#name #ty_generics)
+    }
+}
+
+fn is_phantom_data(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => matches!(
+            path.path.segments.last(),
+            Some(segment) if segment.ident == "PhantomData"
+        ),
+        _ => false,
+    }
+}
+
+/// Whether the field is marked with `#[humanbyte(bytes)]`.
+fn has_bytes_marker(field: &syn::Field) -> syn::Result<bool> {
+    let mut marked = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("humanbyte"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bytes") {
+                marked = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported humanbyte field attribute, expected `bytes`"))
+            }
+        })?;
+    }
+    Ok(marked)
+}
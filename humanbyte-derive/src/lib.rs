@@ -1,33 +1,52 @@
+mod input;
+mod literal;
+
+use input::{Convention, HumanByteInput, Inner};
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{parse_macro_input, parse_quote, DeriveInput};
 
-#[proc_macro_derive(HumanByte)]
-pub fn humanbyte(input: TokenStream) -> TokenStream {
-    let input_str = input.to_string();
-    let constructor = humanbyte_constructor(input_str.parse().unwrap());
-    let display = humanbyte_display(input_str.parse().unwrap());
-    let parse = humanbyte_parse(input_str.parse().unwrap());
-    let ops = humanbyte_ops(input_str.parse().unwrap());
-    let fromstr = humanbyte_fromstr(input_str.parse().unwrap());
-
-    let mut combined = format!("{}{}{}{}{}", constructor, display, parse, ops, fromstr);
-    if cfg!(feature = "serde") {
-        let serde = humanbyte_serde(input_str.parse().unwrap());
-        combined = format!("{}{}", combined, serde);
+/// Parses the derive input and runs `generate` on it, reporting unsupported shapes as compile errors.
+fn expand(input: TokenStream, generate: fn(&HumanByteInput) -> TokenStream2) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match HumanByteInput::parse(&input) {
+        Ok(input) => TokenStream::from(generate(&input)),
+        Err(error) => TokenStream::from(error.to_compile_error()),
     }
-    combined.parse().unwrap()
 }
 
-#[proc_macro_derive(HumanByteConstructor)]
+#[proc_macro_derive(HumanByte, attributes(humanbyte))]
+pub fn humanbyte(input: TokenStream) -> TokenStream {
+    expand(input, |input| {
+        let mut combined = constructor(input);
+        combined.extend(display(input));
+        combined.extend(parse(input));
+        combined.extend(ops(input));
+        combined.extend(fromstr(input));
+        combined.extend(parse_range(input));
+        if cfg!(feature = "serde") {
+            combined.extend(serde(input));
+        }
+        combined
+    })
+}
+
+#[proc_macro_derive(HumanByteConstructor, attributes(humanbyte))]
 pub fn humanbyte_constructor(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
+    expand(input, constructor)
+}
 
-    // Define units with their multipliers and descriptions
-    let units = vec![
-        ("b", "1", "bytes"),
+fn constructor(input: &HumanByteInput) -> TokenStream2 {
+    let name = &input.name;
+    let member = &input.member;
+    let inner = input.inner.ident();
+    let constness = input.constness();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Define units with their multipliers in bytes and descriptions. A single bit is not a whole
+    // number of bytes, so bits start at kilobits.
+    let mut units = vec![
         ("kb", "::humanbyte::KB", "kilobytes"),
         ("kib", "::humanbyte::KIB", "kibibytes"),
         ("mb", "::humanbyte::MB", "megabytes"),
@@ -38,337 +57,935 @@ pub fn humanbyte_constructor(input: TokenStream) -> TokenStream {
         ("tib", "::humanbyte::TIB", "tebibytes"),
         ("pb", "::humanbyte::PB", "petabytes"),
         ("pib", "::humanbyte::PIB", "pebibytes"),
+        ("eb", "::humanbyte::EB", "exabytes"),
+        ("eib", "::humanbyte::EIB", "exbibytes"),
+        ("kbit", "(::humanbyte::KB / 8)", "kilobits"),
+        ("kibit", "(::humanbyte::KIB / 8)", "kibibits"),
+        ("mbit", "(::humanbyte::MB / 8)", "megabits"),
+        ("mibit", "(::humanbyte::MIB / 8)", "mebibits"),
+        ("gbit", "(::humanbyte::GB / 8)", "gigabits"),
+        ("gibit", "(::humanbyte::GIB / 8)", "gibibits"),
+        ("tbit", "(::humanbyte::TB / 8)", "terabits"),
+        ("tibit", "(::humanbyte::TIB / 8)", "tebibits"),
+        ("pbit", "(::humanbyte::PB / 8)", "petabits"),
+        ("pibit", "(::humanbyte::PIB / 8)", "pebibits"),
+        ("ebit", "(::humanbyte::EB / 8)", "exabits"),
+        ("eibit", "(::humanbyte::EIB / 8)", "exbibits"),
     ];
+    // Units beyond exabytes do not fit into anything smaller than a `u128`
+    if input.inner == Inner::U128 {
+        units.extend([
+            ("zb", "::humanbyte::ZB", "zettabytes"),
+            ("zib", "::humanbyte::ZIB", "zebibytes"),
+            ("yb", "::humanbyte::YB", "yottabytes"),
+            ("yib", "::humanbyte::YIB", "yobibytes"),
+            ("rb", "::humanbyte::RB", "ronnabytes"),
+            ("rib", "::humanbyte::RIB", "robibytes"),
+            ("qb", "::humanbyte::QB", "quettabytes"),
+            ("qib", "::humanbyte::QIB", "quebibytes"),
+            ("zbit", "(::humanbyte::ZB / 8)", "zettabits"),
+            ("zibit", "(::humanbyte::ZIB / 8)", "zebibits"),
+            ("ybit", "(::humanbyte::YB / 8)", "yottabits"),
+            ("yibit", "(::humanbyte::YIB / 8)", "yobibits"),
+            ("rbit", "(::humanbyte::RB / 8)", "ronnabits"),
+            ("ribit", "(::humanbyte::RIB / 8)", "robibits"),
+            ("qbit", "(::humanbyte::QB / 8)", "quettabits"),
+            ("qibit", "(::humanbyte::QIB / 8)", "quebibits"),
+        ]);
+    }
 
     // Generate methods
     let methods = units.iter().map(|(fn_name, multiplier, description)| {
         // Create an identifier for the method name
         let method_name = syn::Ident::new(fn_name, Span::call_site());
+        let checked_method_name = format_ident!("checked_{}", fn_name);
 
         // Parse the multiplier into an expression
         let multiplier_expr: syn::Expr = syn::parse_str(multiplier).unwrap();
 
         // Generate the documentation comment
-        let doc_comment = format!("Construct `{}` given an amount of {}.", name, description);
+        let doc_comment = format!(
+            "Construct `{}` given an amount of {}.\n\n\
+             # Panics\n\n\
+             Panics if the number of bytes does not fit into a `{}`.",
+            name,
+            description,
+            input.inner.name()
+        );
+        let checked_doc_comment = format!(
+            "Construct `{}` given an amount of {}, returning `None` if the number of bytes does \
+             not fit into a `{}`.",
+            name,
+            description,
+            input.inner.name()
+        );
+        let overflow = format!("overflow constructing `{}` from {}", name, description);
+        let construct = input.construct(quote!(bytes as #inner));
 
         // Generate the method using quote!
         quote! {
             #[doc = #doc_comment]
             #[inline(always)]
-            pub const fn #method_name(size: u64) -> Self {
-                Self(size * #multiplier_expr)
+            pub #constness fn #method_name(size: #inner) -> Self {
+                match Self::#checked_method_name(size) {
+                    Some(size) => size,
+                    None => panic!(#overflow),
+                }
+            }
+
+            #[doc = #checked_doc_comment]
+            #[inline(always)]
+            pub #constness fn #checked_method_name(size: #inner) -> Option<Self> {
+                match (size as u128).checked_mul(#multiplier_expr as u128) {
+                    Some(bytes) if bytes <= #inner::MAX as u128 => Some(#construct),
+                    _ => None,
+                }
             }
         }
     });
 
-    let expanded = quote! {
-        impl #name {
+    let doc_comment = format!("Construct `{}` given an amount of bytes.", name);
+    let construct = input.construct(quote!(size));
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #doc_comment]
+            #[inline(always)]
+            pub #constness fn b(size: #inner) -> Self {
+                #construct
+            }
+
             #(#methods)*
         }
 
-        impl From<u64> for #name {
-            fn from(size: u64) -> #name {
-                Self(size)
+        impl #impl_generics From<#inner> for #name #ty_generics #where_clause {
+            fn from(size: #inner) -> Self {
+                #construct
             }
         }
-    };
 
-    TokenStream::from(expanded)
+        impl #impl_generics ::humanbyte::ByteNewType for #name #ty_generics #where_clause {
+            type Inner = #inner;
+
+            #[inline(always)]
+            fn from_inner(size: #inner) -> Self {
+                #construct
+            }
+
+            #[inline(always)]
+            fn inner(&self) -> #inner {
+                self.#member
+            }
+        }
+    }
 }
 
-#[proc_macro_derive(HumanByteOps)]
+#[proc_macro_derive(HumanByteOps, attributes(humanbyte))]
 pub fn humanbyte_ops(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
+    expand(input, ops)
+}
 
-    let expanded = quote! {
-        impl core::ops::Add<#name> for #name {
-            type Output = #name;
+fn ops(input: &HumanByteInput) -> TokenStream2 {
+    // `stats` collects the sizes, so it needs the `alloc` feature of humanbyte
+    let stats = cfg!(feature = "alloc").then(|| {
+        quote! {
+            /// Computes the count, total, min, max, mean and median of `sizes`, see
+            /// [`humanbyte::stats`].
+            pub fn stats<I>(sizes: I) -> Option<::humanbyte::Stats<Self>>
+            where
+                I: IntoIterator,
+                I::Item: core::borrow::Borrow<Self>,
+            {
+                ::humanbyte::stats(sizes)
+            }
+        }
+    });
+    let name = &input.name;
+    let member = &input.member;
+    let inner = input.inner.ident();
+    let constness = input.constness();
+    let ty = input.ty();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let rhs_generics = input.generics_with(parse_quote!(__Rhs: Into<#inner>));
+    let (rhs_impl_generics, _, _) = rhs_generics.split_for_impl();
+    let ref_generics = input.generics_with(parse_quote!('__a));
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
 
-            #[inline(always)]
-            fn add(self, rhs: #name) -> #name {
-                #name(self.0 + rhs.0)
+    // Primitives on the left hand side, e.g. `2u64 * size`, are only supported when they convert into
+    // the inner type without loss.
+    let primitive_ops = input.inner.lossless_sources().iter().map(|primitive| {
+        let primitive = primitive.ident();
+        let add = input.construct_from(quote!(rhs), quote!(rhs.#member + #inner::from(self)));
+        let mul = input.construct_from(quote!(rhs), quote!(rhs.#member * #inner::from(self)));
+        quote! {
+            impl #impl_generics core::ops::Add<#ty> for #primitive #where_clause {
+                type Output = #ty;
+                #[inline(always)]
+                fn add(self, rhs: #ty) -> #ty {
+                    #add
+                }
+            }
+
+            impl #impl_generics core::ops::Mul<#ty> for #primitive #where_clause {
+                type Output = #ty;
+                #[inline(always)]
+                fn mul(self, rhs: #ty) -> #ty {
+                    #mul
+                }
             }
         }
+    });
 
-        impl core::ops::AddAssign<#name> for #name {
-            #[inline(always)]
-            fn add_assign(&mut self, rhs: #name) {
-                self.0 += rhs.0
+    // Division by a plain factor is implemented per primitive rather than for any `Into<inner>`, which
+    // leaves `size / duration` free for `HumanByteRate`.
+    let divisions = input.inner.lossless_sources().iter().map(|primitive| {
+        let primitive = primitive.ident();
+        let div = input.construct_from(quote!(self), quote!(self.#member / #inner::from(rhs)));
+        quote! {
+            /// Splits a size into `rhs` equal parts, rounding down.
+            impl #impl_generics core::ops::Div<#primitive> for #name #ty_generics #where_clause {
+                type Output = Self;
+
+                #[inline(always)]
+                fn div(self, rhs: #primitive) -> Self {
+                    #div
+                }
+            }
+
+            impl #impl_generics core::ops::DivAssign<#primitive> for #name #ty_generics #where_clause {
+                #[inline(always)]
+                fn div_assign(&mut self, rhs: #primitive) {
+                    self.#member /= #inner::from(rhs);
+                }
             }
         }
+    });
 
-        impl<T> core::ops::Add<T> for #name
-        where
-            T: Into<u64>,
-        {
-            type Output = #name;
+    // Checked, saturating, wrapping and overflowing variants of the arithmetic operators, mirroring
+    // the methods on the primitive integer types. Addition and subtraction take another size, while
+    // multiplication and division take a plain factor.
+    let arithmetic = [
+        ("add", "addition", true),
+        ("sub", "subtraction", true),
+        ("mul", "multiplication", false),
+        ("div", "division", false),
+    ]
+    .iter()
+    .map(|(op, description, sized_rhs)| {
+        let (rhs_type, rhs) = if *sized_rhs {
+            (quote!(Self), quote!(rhs.#member))
+        } else {
+            (quote!(#inner), quote!(rhs))
+        };
+        let checked = format_ident!("checked_{}", op);
+        let saturating = format_ident!("saturating_{}", op);
+        let wrapping = format_ident!("wrapping_{}", op);
+        let overflowing = format_ident!("overflowing_{}", op);
+        let division = *op == "div";
+        let checked_doc = if division {
+            "Checked division. Returns `None` if `rhs` is zero.".to_string()
+        } else {
+            format!("Checked {}. Returns `None` if the result overflows.", description)
+        };
+        let saturating_doc = if division {
+            "Saturating division, which cannot overflow for unsigned sizes. Panics if `rhs` is zero."
+                .to_string()
+        } else {
+            format!(
+                "Saturating {}. Clamps the result at zero or the maximum size instead of overflowing.",
+                description
+            )
+        };
+        let wrapping_doc = if division {
+            "Wrapping division, which cannot overflow for unsigned sizes. Panics if `rhs` is zero."
+                .to_string()
+        } else {
+            format!("Wrapping {}. Wraps around at the boundary of the inner type.", description)
+        };
+        let overflowing_doc = format!(
+            "Calculates the {} of `self` and `rhs`, returning the wrapped result and whether an \
+             overflow occurred.",
+            description
+        );
+        let construct = input.construct_from(quote!(self), quote!(bytes));
+        quote! {
+            #[doc = #checked_doc]
             #[inline(always)]
-            fn add(self, rhs: T) -> #name {
-                #name(self.0 + (rhs.into()))
+            pub #constness fn #checked(self, rhs: #rhs_type) -> Option<Self> {
+                match self.#member.#checked(#rhs) {
+                    Some(bytes) => Some(#construct),
+                    None => None,
+                }
             }
-        }
 
-        impl<T> core::ops::AddAssign<T> for #name
-        where
-            T: Into<u64>,
-        {
+            #[doc = #saturating_doc]
             #[inline(always)]
-            fn add_assign(&mut self, rhs: T) {
-                self.0 += rhs.into();
+            pub #constness fn #saturating(self, rhs: #rhs_type) -> Self {
+                let bytes = self.#member.#saturating(#rhs);
+                #construct
+            }
+
+            #[doc = #wrapping_doc]
+            #[inline(always)]
+            pub #constness fn #wrapping(self, rhs: #rhs_type) -> Self {
+                let bytes = self.#member.#wrapping(#rhs);
+                #construct
+            }
+
+            #[doc = #overflowing_doc]
+            #[inline(always)]
+            pub #constness fn #overflowing(self, rhs: #rhs_type) -> (Self, bool) {
+                let (bytes, overflow) = self.#member.#overflowing(#rhs);
+                (#construct, overflow)
             }
         }
+    });
 
-        impl core::ops::Sub<#name> for #name {
-            type Output = #name;
+    let add = input.construct_from(quote!(self), quote!(self.#member + rhs.#member));
+    let add_rhs = input.construct_from(quote!(self), quote!(self.#member + rhs.into()));
+    let sub = input.construct_from(quote!(self), quote!(self.#member - rhs.#member));
+    let sub_rhs = input.construct_from(quote!(self), quote!(self.#member - rhs.into()));
+    let mul_rhs = input.construct_from(quote!(self), quote!(self.#member * rhs.into()));
+    let rem = input.construct_from(quote!(self), quote!(self.#member % rhs.#member));
+    let rem_rhs = input.construct_from(quote!(self), quote!(self.#member % rhs.into()));
+    let sum = input.construct(quote!(iter.map(|size| size.#member).sum()));
+    let try_sum = input.construct(quote!(bytes));
+    let abs_diff = input.construct_from(quote!(self), quote!(bytes));
+
+    quote! {
+        impl #impl_generics core::ops::Add<Self> for #name #ty_generics #where_clause {
+            type Output = Self;
 
             #[inline(always)]
-            fn sub(self, rhs: #name) -> #name {
-                #name(self.0 - rhs.0)
+            fn add(self, rhs: Self) -> Self {
+                #add
             }
         }
 
-        impl core::ops::SubAssign<#name> for #name {
+        impl #impl_generics core::ops::AddAssign<Self> for #name #ty_generics #where_clause {
             #[inline(always)]
-            fn sub_assign(&mut self, rhs: #name) {
-                self.0 -= rhs.0
+            fn add_assign(&mut self, rhs: Self) {
+                self.#member += rhs.#member
             }
         }
 
-        impl<T> core::ops::Sub<T> for #name
-        where
-            T: Into<u64>,
-        {
-            type Output = #name;
+        impl #rhs_impl_generics core::ops::Add<__Rhs> for #name #ty_generics #where_clause {
+            type Output = Self;
+            #[inline(always)]
+            fn add(self, rhs: __Rhs) -> Self {
+                #add_rhs
+            }
+        }
 
+        impl #rhs_impl_generics core::ops::AddAssign<__Rhs> for #name #ty_generics #where_clause {
             #[inline(always)]
-            fn sub(self, rhs: T) -> #name {
-                #name(self.0 - (rhs.into()))
+            fn add_assign(&mut self, rhs: __Rhs) {
+                self.#member += rhs.into();
             }
         }
 
-        impl<T> core::ops::SubAssign<T> for #name
-        where
-            T: Into<u64>,
-        {
+        impl #impl_generics core::ops::Sub<Self> for #name #ty_generics #where_clause {
+            type Output = Self;
+
             #[inline(always)]
-            fn sub_assign(&mut self, rhs: T) {
-                self.0 -= rhs.into();
+            fn sub(self, rhs: Self) -> Self {
+                #sub
             }
         }
 
-        impl<T> core::ops::Mul<T> for #name
-        where
-            T: Into<u64>,
-        {
-            type Output = #name;
+        impl #impl_generics core::ops::SubAssign<Self> for #name #ty_generics #where_clause {
             #[inline(always)]
-            fn mul(self, rhs: T) -> #name {
-                #name(self.0 * rhs.into())
+            fn sub_assign(&mut self, rhs: Self) {
+                self.#member -= rhs.#member
             }
         }
 
-        impl<T> core::ops::MulAssign<T> for #name
-        where
-            T: Into<u64>,
-        {
+        impl #rhs_impl_generics core::ops::Sub<__Rhs> for #name #ty_generics #where_clause {
+            type Output = Self;
+
             #[inline(always)]
-            fn mul_assign(&mut self, rhs: T) {
-                self.0 *= rhs.into();
+            fn sub(self, rhs: __Rhs) -> Self {
+                #sub_rhs
             }
         }
 
-        impl core::ops::Add<#name> for u64 {
-            type Output = #name;
+        impl #rhs_impl_generics core::ops::SubAssign<__Rhs> for #name #ty_generics #where_clause {
             #[inline(always)]
-            fn add(self, rhs: #name) -> #name {
-                #name(rhs.0 + self)
+            fn sub_assign(&mut self, rhs: __Rhs) {
+                self.#member -= rhs.into();
             }
         }
 
-        impl core::ops::Add<#name> for u32 {
-            type Output = #name;
+        impl #rhs_impl_generics core::ops::Mul<__Rhs> for #name #ty_generics #where_clause {
+            type Output = Self;
             #[inline(always)]
-            fn add(self, rhs: #name) -> #name {
-                #name(rhs.0 + (self as u64))
+            fn mul(self, rhs: __Rhs) -> Self {
+                #mul_rhs
             }
         }
 
-        impl core::ops::Add<#name> for u16 {
-            type Output = #name;
+        impl #rhs_impl_generics core::ops::MulAssign<__Rhs> for #name #ty_generics #where_clause {
             #[inline(always)]
-            fn add(self, rhs: #name) -> #name {
-                #name(rhs.0 + (self as u64))
+            fn mul_assign(&mut self, rhs: __Rhs) {
+                self.#member *= rhs.into();
             }
         }
 
-        impl core::ops::Add<#name> for u8 {
-            type Output = #name;
+        /// Divides two sizes, returning how many times `rhs` fits into `self`.
+        impl #impl_generics core::ops::Div<Self> for #name #ty_generics #where_clause {
+            type Output = #inner;
+
             #[inline(always)]
-            fn add(self, rhs: #name) -> #name {
-                #name(rhs.0 + (self as u64))
+            fn div(self, rhs: Self) -> #inner {
+                self.#member / rhs.#member
             }
         }
 
-        impl core::ops::Mul<#name> for u64 {
-            type Output = #name;
+        #(#divisions)*
+
+        /// The bytes left over after fitting as many `rhs` into `self` as possible.
+        impl #impl_generics core::ops::Rem<Self> for #name #ty_generics #where_clause {
+            type Output = Self;
+
             #[inline(always)]
-            fn mul(self, rhs: #name) -> #name {
-                #name(rhs.0 * self)
+            fn rem(self, rhs: Self) -> Self {
+                #rem
             }
         }
 
-        impl core::ops::Mul<#name> for u32 {
-            type Output = #name;
+        impl #impl_generics core::ops::RemAssign<Self> for #name #ty_generics #where_clause {
             #[inline(always)]
-            fn mul(self, rhs: #name) -> #name {
-                #name(rhs.0 * (self as u64))
+            fn rem_assign(&mut self, rhs: Self) {
+                self.#member %= rhs.#member
             }
         }
 
-        impl core::ops::Mul<#name> for u16 {
-            type Output = #name;
+        impl #rhs_impl_generics core::ops::Rem<__Rhs> for #name #ty_generics #where_clause {
+            type Output = Self;
+
             #[inline(always)]
-            fn mul(self, rhs: #name) -> #name {
-                #name(rhs.0 * (self as u64))
+            fn rem(self, rhs: __Rhs) -> Self {
+                #rem_rhs
             }
         }
 
-        impl core::ops::Mul<#name> for u8 {
-            type Output = #name;
+        impl #rhs_impl_generics core::ops::RemAssign<__Rhs> for #name #ty_generics #where_clause {
             #[inline(always)]
-            fn mul(self, rhs: #name) -> #name {
-                #name(rhs.0 * (self as u64))
+            fn rem_assign(&mut self, rhs: __Rhs) {
+                self.#member %= rhs.into();
             }
         }
 
-        impl #name {
-            /// Provides `HumanByteRange` with explicit lower and upper bounds.
+        impl #impl_generics core::iter::Sum<Self> for #name #ty_generics #where_clause {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                #sum
+            }
+        }
+
+        impl #ref_impl_generics core::iter::Sum<&'__a Self> for #name #ty_generics #where_clause {
+            fn sum<I: Iterator<Item = &'__a Self>>(iter: I) -> Self {
+                #sum
+            }
+        }
+
+        #(#primitive_ops)*
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#arithmetic)*
+
+            /// Returns how many `rhs` are needed to cover `self`, i.e. the division rounded up.
+            ///
+            /// Panics if `rhs` is zero.
+            #[inline(always)]
+            pub #constness fn div_ceil(self, rhs: Self) -> #inner {
+                let quotient = self.#member / rhs.#member;
+                if self.#member % rhs.#member == 0 {
+                    quotient
+                } else {
+                    quotient + 1
+                }
+            }
+
+            /// Returns the absolute difference between `self` and `rhs`, which never underflows unlike
+            /// subtraction.
+            #[inline(always)]
+            pub #constness fn abs_diff(self, rhs: Self) -> Self {
+                let bytes = self.#member.abs_diff(rhs.#member);
+                #abs_diff
+            }
+
+            /// Returns the signed difference `self - rhs`, e.g. the growth since an earlier size.
+            ///
+            /// Panics if the difference does not fit into an `i128`, which is only possible for
+            /// `u128` sizes.
+            #[inline(always)]
+            pub fn signed_sub(self, rhs: Self) -> ::humanbyte::ByteDelta {
+                ::humanbyte::ByteDelta::between(rhs.#member, self.#member)
+            }
+
+            /// Sums `sizes`, returning `None` instead of overflowing.
+            pub fn try_sum<I>(sizes: I) -> Option<Self>
+            where
+                I: IntoIterator,
+                I::Item: core::borrow::Borrow<Self>,
+            {
+                let mut bytes: #inner = 0;
+                for size in sizes {
+                    bytes = bytes.checked_add(core::borrow::Borrow::<Self>::borrow(&size).#member)?;
+                }
+                Some(#try_sum)
+            }
+
+            #stats
+
+            /// Returns `self` as a fraction of `other`, e.g. `0.25` when a quarter of a quota is used.
+            #[inline(always)]
+            pub fn ratio(&self, other: &Self) -> f64 {
+                self.#member as f64 / other.#member as f64
+            }
+
+            /// Returns `self` as a percentage of `other`, e.g. `25.0` when a quarter of a quota is used.
+            #[inline(always)]
+            pub fn percentage(&self, other: &Self) -> f64 {
+                self.ratio(other) * 100.0
+            }
+
+            /// Provides `HumanByteRange` including both `start` and `stop`, e.g. `1.0 MiB..=4.0 GiB`.
             pub fn range<I: Into<Self>>(start: I, stop: I) -> ::humanbyte::HumanByteRange<Self> {
                 ::humanbyte::HumanByteRange::new(Some(start), Some(stop))
             }
 
-            /// Provides `HumanByteRange` with explicit lower bound. Upper bound is set to `u64::MAX`.
+            /// Provides `HumanByteRange` from `start` on without an upper bound, e.g. `1.0 MiB..`.
             pub fn range_start<I: Into<Self>>(start: I) -> ::humanbyte::HumanByteRange<Self> {
                 ::humanbyte::HumanByteRange::new(Some(start), None)
             }
 
-            /// Provides `HumanByteRange` with explicit lower bound. Upper bound is set to `u64::MAX`.
+            /// Provides `HumanByteRange` up to and including `stop` without a lower bound, e.g.
+            /// `..=4.0 GiB`.
             pub fn range_stop<I: Into<Self>>(stop: I) -> ::humanbyte::HumanByteRange<Self> {
-                ::humanbyte::HumanByteRange::new(None, Some(stop.into()))
+                ::humanbyte::HumanByteRange::new(None, Some(stop))
+            }
+
+            /// Provides `HumanByteRange` from `start` up to but excluding `end`, e.g.
+            /// `1.0 MiB..4.0 GiB`, as used for size buckets.
+            pub fn range_until<I: Into<Self>>(start: I, end: I) -> ::humanbyte::HumanByteRange<Self> {
+                ::humanbyte::HumanByteRange::from(start.into()..end.into())
             }
-        }
-    };
 
-    TokenStream::from(expanded)
+            /// Provides the `(offset, len)` of consecutive chunks of `size` covering `0 B..self`, the
+            /// last of which may be shorter, see [`humanbyte::HumanByteRange::chunks`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if `size` is zero.
+            pub fn chunks<I: Into<Self>>(self, size: I) -> ::humanbyte::Chunks<Self> {
+                ::humanbyte::HumanByteRange::from(..self).chunks(size.into())
+            }
+
+            /// Provides the `(offset, len)` of at most `parts` pieces of nearly equal length
+            /// covering `0 B..self`, see [`humanbyte::HumanByteRange::split_into`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if `parts` is zero.
+            pub fn split_into(self, parts: usize) -> ::humanbyte::SplitInto<Self> {
+                ::humanbyte::HumanByteRange::from(..self)
+                    .split_into(parts)
+                    .expect("pieces of `..self` fit into the inner type")
+            }
+        }
+    }
 }
 
-#[proc_macro_derive(HumanByteDisplay)]
+#[proc_macro_derive(HumanByteDisplay, attributes(humanbyte))]
 pub fn humanbyte_display(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
+    expand(input, display)
+}
 
-    let expanded = quote! {
-        impl core::fmt::Display for #name {
+fn display(input: &HumanByteInput) -> TokenStream2 {
+    let name = &input.name;
+    let member = &input.member;
+    let format = input.format.to_tokens();
+    let alternate = input.format.alternate().to_tokens();
+    let precision = input.precision;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        /// The precision flag sets the number of decimal places, e.g. `{:.3}`, and the alternate flag
+        /// switches between IEC and SI units, e.g. `{:#}`.
+        impl #impl_generics core::fmt::Display for #name #ty_generics #where_clause {
             fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-                f.pad(&::humanbyte::to_string(self.0, ::humanbyte::Format::IEC))
+                let format = if f.alternate() { #alternate } else { #format };
+                let precision = f.precision().unwrap_or(#precision);
+                core::fmt::Display::fmt(
+                    &::humanbyte::FormattedSize::new(self.#member, format, precision),
+                    f,
+                )
             }
         }
 
-        impl core::fmt::Debug for #name {
+        impl #impl_generics core::fmt::Debug for #name #ty_generics #where_clause {
             fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
                 write!(f, "{}", self)
             }
         }
-    };
-
-    TokenStream::from(expanded)
+    }
 }
 
-#[proc_macro_derive(HumanByteFromStr)]
+#[proc_macro_derive(HumanByteFromStr, attributes(humanbyte))]
 pub fn humanbyte_fromstr(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
-
-    let expanded = quote! {
-        impl core::str::FromStr for #name {
-            type Err = ::humanbyte::String;
+    expand(input, fromstr)
+}
 
-            fn from_str(value: &str) -> core::result::Result<Self, Self::Err> {
-                if let Ok(v) = value.parse::<u64>() {
-                    return Ok(Self(v));
-                }
-                let number = ::humanbyte::take_while(value, |c| c.is_ascii_digit() || c == '.');
-                match number.parse::<f64>() {
-                    Ok(v) => {
-                        let suffix = ::humanbyte::skip_while(&value[number.len()..], char::is_whitespace);
-                        match suffix.parse::<::humanbyte::Unit>() {
-                            Ok(u) => Ok(Self((v * u64::from(u) as f64) as u64)),
-                            Err(error) => Err(::humanbyte::format!(
-                                "couldn't parse {:?} into a known SI unit, {}",
-                                suffix, error
-                            )),
-                        }
+fn fromstr(input: &HumanByteInput) -> TokenStream2 {
+    let name = &input.name;
+    let inner = input.inner.ident();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let construct = input.construct(quote!(size));
+    let convention = input.convention.to_tokens();
+    let constness = input.constness();
+    // the const parser only knows the standard units
+    let parse_const = matches!(input.convention, Convention::Standard).then(|| {
+        let panic_doc = format!(
+            "Panics, at compile time when initializing a `const`, if `value` is not a valid size or \
+             does not fit into a `{}`.",
+            input.inner.name()
+        );
+        quote! {
+            /// Parses a size in a `const` context, e.g.
+            /// `const LIMIT: Self = Self::parse_const("512 MiB")`, see [`humanbyte::parse_bytes`].
+            ///
+            /// # Panics
+            ///
+            #[doc = #panic_doc]
+            pub #constness fn parse_const(value: &str) -> Self {
+                match ::humanbyte::parse_bytes_u128(value) {
+                    Ok(bytes) if bytes <= #inner::MAX as u128 => {
+                        let size = bytes as #inner;
+                        #construct
                     }
-                    Err(error) => Err(::humanbyte::format!(
-                        "couldn't parse {:?} into a ByteSize, {}",
-                        value, error
-                    )),
+                    Ok(_) => panic!("{}", ::humanbyte::ParseErrorKind::Overflow.as_str()),
+                    Err(kind) => panic!("{}", kind.as_str()),
                 }
             }
         }
-    };
+    });
 
-    TokenStream::from(expanded)
+    quote! {
+        /// Parses sizes exactly, see [`humanbyte::parse`] for the accepted syntax.
+        impl #impl_generics core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::humanbyte::ParseError;
+
+            fn from_str(value: &str) -> core::result::Result<Self, Self::Err> {
+                Self::parse_with(&::humanbyte::Parser::new().convention(#convention), value)
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Parses a size accepting only canonical unit symbols such as `kB` or `MiB`, see
+            /// [`humanbyte::Parser::strict`].
+            pub fn parse_strict(value: &str) -> core::result::Result<Self, ::humanbyte::ParseError> {
+                Self::parse_with(&::humanbyte::Parser::strict().convention(#convention), value)
+            }
+
+            /// Parses a size with the policies of `parser`.
+            pub fn parse_with(
+                parser: &::humanbyte::Parser,
+                value: &str,
+            ) -> core::result::Result<Self, ::humanbyte::ParseError> {
+                parser.parse::<#inner>(value).map(|size| #construct)
+            }
+
+            #parse_const
+        }
+    }
 }
 
-#[proc_macro_derive(HumanByteParse)]
+/// Parsing of ranges, which needs both `ByteNewType` and `FromStr`, so only the combined derive
+/// generates it.
+fn parse_range(input: &HumanByteInput) -> TokenStream2 {
+    let name = &input.name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Parses a range such as `10 MiB..1 GiB`, `>=500M` or `<4k`, see
+            /// [`humanbyte::HumanByteRange`] for the accepted syntax.
+            pub fn parse_range(
+                value: &str,
+            ) -> core::result::Result<::humanbyte::HumanByteRange<Self>, ::humanbyte::ParseError> {
+                value.parse()
+            }
+        }
+    }
+}
+
+#[proc_macro_derive(HumanByteParse, attributes(humanbyte))]
 pub fn humanbyte_parse(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
+    expand(input, parse)
+}
 
-    let expanded = quote! {
-        impl #name {
+fn parse(input: &HumanByteInput) -> TokenStream2 {
+    let name = &input.name;
+    let member = &input.member;
+    let format = input.format.to_tokens();
+    let precision = input.precision;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Accessors for the inner type, plus lossless widenings to `u64` and `u128`.
+    let mut accessors = vec![input.inner];
+    if input.inner.fits_u64() && input.inner != Inner::U64 {
+        accessors.push(Inner::U64);
+    }
+    if input.inner != Inner::U128 {
+        accessors.push(Inner::U128);
+    }
+    let accessors = accessors.into_iter().map(|target| {
+        let method_name = format_ident!("as_{}", target.name());
+        let target_type = target.ident();
+        let doc_comment = if target == input.inner {
+            format!("Returns the inner {} value.", target.name())
+        } else {
+            format!("Returns the inner value as a {}.", target.name())
+        };
+        quote! {
+            #[doc = #doc_comment]
+            #[inline(always)]
+            pub const fn #method_name(&self) -> #target_type {
+                self.#member as #target_type
+            }
+        }
+    });
+
+    // returning `String` needs the `alloc` feature of humanbyte
+    let strings = cfg!(feature = "alloc").then(|| {
+        quote! {
             /// Returns the size as a string with an optional SI unit.
             #[inline(always)]
             pub fn to_string_as(&self, format: ::humanbyte::Format) -> ::humanbyte::String {
-                ::humanbyte::to_string(self.0, format)
+                ::humanbyte::to_string_with_precision(self.#member, format, #precision)
+            }
+
+            /// Returns the size as a string that parses back to exactly the same size, see
+            /// [`humanbyte::to_string_exact`].
+            #[inline(always)]
+            pub fn to_string_exact(&self) -> ::humanbyte::String {
+                ::humanbyte::to_string_exact(self.#member, #format)
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #strings
+
+            /// Writes the size to `out` with the format and precision of `Display`, without
+            /// allocating, see [`humanbyte::write_to`].
+            #[inline(always)]
+            pub fn write_to<W: core::fmt::Write + ?Sized>(&self, out: &mut W) -> core::fmt::Result {
+                ::humanbyte::write_to(out, self.#member, #format, #precision)
             }
 
-            /// Returns the inner u64 value.
+            /// Returns the size formatted on the stack, see [`humanbyte::FormattedSize`].
             #[inline(always)]
-            pub const fn as_u64(&self) -> u64 {
-                self.0
+            pub fn formatted(&self, format: ::humanbyte::Format) -> ::humanbyte::FormattedSize {
+                ::humanbyte::FormattedSize::new(self.#member, format, #precision)
             }
+
+            #(#accessors)*
         }
+    }
+}
+
+/// Derives a transfer rate in bytes per second for the size type named by
+/// `#[humanbyte(size = "Type")]`, including serde support when the `serde` feature is enabled.
+///
+/// As the derive implements `Div<Duration>` for the size type, each size type can have only one rate
+/// type.
+#[proc_macro_derive(HumanByteRate, attributes(humanbyte))]
+pub fn humanbyte_rate(input: TokenStream) -> TokenStream {
+    expand(input, |input| {
+        let mut combined = rate(input);
+        if cfg!(feature = "serde") {
+            combined.extend(serde(input));
+        }
+        combined
+    })
+}
+
+fn rate(input: &HumanByteInput) -> TokenStream2 {
+    let name = &input.name;
+    let member = &input.member;
+    let inner = input.inner.ident();
+    let format = input.format.to_tokens();
+    let alternate = input.format.alternate().to_tokens();
+    let precision = input.precision;
+    let construct = input.construct(quote!(bytes_per_second));
+
+    let Some(size) = &input.size else {
+        return syn::Error::new_spanned(
+            name,
+            "HumanByteRate requires the size type, e.g. `#[humanbyte(size = \"ByteSize\")]`",
+        )
+        .to_compile_error();
     };
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(&input.generics, "HumanByteRate does not support generics")
+            .to_compile_error();
+    }
+    if input.serialize_exact {
+        return syn::Error::new_spanned(
+            name,
+            "HumanByteRate does not support `serialize = \"exact\"`",
+        )
+        .to_compile_error();
+    }
+
+    // returning `String` needs the `alloc` feature of humanbyte
+    let strings = cfg!(feature = "alloc").then(|| {
+        quote! {
+            /// Returns the rate as a string such as `12.4 MiB/s`.
+            #[inline(always)]
+            pub fn to_string_as(&self, format: ::humanbyte::Format) -> ::humanbyte::String {
+                ::humanbyte::rate_to_string_with_precision(self.#member, format, #precision)
+            }
 
-    TokenStream::from(expanded)
+            /// Returns the rate in bits per second as a string such as `99.2 Mbit/s`.
+            #[inline(always)]
+            pub fn to_bits_string_as(&self, format: ::humanbyte::Format) -> ::humanbyte::String {
+                ::humanbyte::rate_to_bits_string_with_precision(self.#member, format, #precision)
+            }
+        }
+    });
+
+    quote! {
+        impl #name {
+            /// Creates a rate of `size` per second.
+            ///
+            /// Panics if the size does not fit into the rate type.
+            #[inline(always)]
+            pub fn per_second(size: #size) -> Self {
+                let bytes = ::humanbyte::ByteInt::into_u128(::humanbyte::ByteNewType::inner(&size));
+                let bytes_per_second = <#inner as ::humanbyte::ByteInt>::from_u128(bytes)
+                    .expect("transfer rate overflows");
+                #construct
+            }
+
+            /// Returns the number of bytes per second.
+            #[inline(always)]
+            pub const fn bytes_per_second(&self) -> #inner {
+                self.#member
+            }
+
+            #strings
+        }
+
+        impl From<#inner> for #name {
+            #[inline(always)]
+            fn from(bytes_per_second: #inner) -> Self {
+                #construct
+            }
+        }
+
+        /// Panics if `rhs` is zero or the rate does not fit into the rate type.
+        impl core::ops::Div<core::time::Duration> for #size {
+            type Output = #name;
+
+            fn div(self, rhs: core::time::Duration) -> #name {
+                let bytes = ::humanbyte::ByteInt::into_u128(::humanbyte::ByteNewType::inner(&self));
+                let bytes_per_second = ::humanbyte::transfer_rate(bytes, rhs)
+                    .and_then(<#inner as ::humanbyte::ByteInt>::from_u128)
+                    .expect("transfer rate overflows");
+                #construct
+            }
+        }
+
+        /// Panics if the size does not fit into the size type.
+        impl core::ops::Mul<core::time::Duration> for #name {
+            type Output = #size;
+
+            fn mul(self, rhs: core::time::Duration) -> #size {
+                let bytes = ::humanbyte::transferred(self.#member as u128, rhs)
+                    .and_then(::humanbyte::ByteInt::from_u128)
+                    .expect("transferred size overflows");
+                <#size as ::humanbyte::ByteNewType>::from_inner(bytes)
+            }
+        }
+
+        /// The precision flag sets the number of decimal places, e.g. `{:.3}`, and the alternate flag
+        /// switches between IEC and SI units, e.g. `{:#}`.
+        impl core::fmt::Display for #name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                let format = if f.alternate() { #alternate } else { #format };
+                let precision = f.precision().unwrap_or(#precision);
+                core::fmt::Display::fmt(
+                    &::humanbyte::FormattedSize::rate(self.#member, format, precision),
+                    f,
+                )
+            }
+        }
+
+        impl core::fmt::Debug for #name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "{}", self)
+            }
+        }
+
+        /// Parses rates in bytes or bits per second, see [`humanbyte::parse_rate`].
+        impl core::str::FromStr for #name {
+            type Err = ::humanbyte::ParseError;
+
+            fn from_str(value: &str) -> core::result::Result<Self, Self::Err> {
+                ::humanbyte::parse_rate::<#inner>(value).map(|bytes_per_second| #construct)
+            }
+        }
+    }
 }
 
-#[proc_macro_derive(HumanByteSerde)]
+#[proc_macro_derive(HumanByteSerde, attributes(humanbyte))]
 pub fn humanbyte_serde(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
+    expand(input, serde)
+}
+
+fn serde(input: &HumanByteInput) -> TokenStream2 {
+    let name = &input.name;
+    let member = &input.member;
+    let inner = input.inner.ident();
+    let ty = input.ty();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let de_generics = input.generics_with(parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+    let construct = input.construct(quote!(val));
+    let serialize_human_readable = if input.serialize_exact {
+        let format = input.format.to_tokens();
+        quote!(::humanbyte::to_string_exact(self.#member, #format))
+    } else {
+        quote!(self.to_string())
+    };
+    // `usize` is serialized as a `u64` by serde
+    let deserialize_inner = match input.inner {
+        Inner::Usize => format_ident!("deserialize_u64"),
+        inner => format_ident!("deserialize_{}", inner.name()),
+    };
 
-    let expanded = quote! {
-        impl<'de> ::humanbyte::serde::Deserialize<'de> for #name {
+    quote! {
+        impl #de_impl_generics ::humanbyte::serde::Deserialize<'de> for #name #ty_generics #where_clause {
             fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
             where
                 D: ::humanbyte::serde::Deserializer<'de>,
             {
-                struct ByteSizeVistor;
+                struct ByteSizeVistor #impl_generics (core::marker::PhantomData<#ty>) #where_clause;
 
-                impl<'de> ::humanbyte::serde::de::Visitor<'de> for ByteSizeVistor {
-                    type Value = #name;
+                impl #de_impl_generics ::humanbyte::serde::de::Visitor<'de> for ByteSizeVistor #ty_generics #where_clause {
+                    type Value = #ty;
 
                     fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                         formatter.write_str("an integer or string")
                     }
 
                     fn visit_i64<E: ::humanbyte::serde::de::Error>(self, value: i64) -> core::result::Result<Self::Value, E> {
-                        if let Ok(val) = u64::try_from(value) {
-                            Ok(#name(val))
+                        if let Ok(val) = #inner::try_from(value) {
+                            Ok(#construct)
                         } else {
                             Err(E::invalid_value(
                                 ::humanbyte::serde::de::Unexpected::Signed(value),
@@ -378,7 +995,25 @@ pub fn humanbyte_serde(input: TokenStream) -> TokenStream {
                     }
 
                     fn visit_u64<E: ::humanbyte::serde::de::Error>(self, value: u64) -> core::result::Result<Self::Value, E> {
-                        Ok(#name(value))
+                        if let Ok(val) = #inner::try_from(value) {
+                            Ok(#construct)
+                        } else {
+                            Err(E::invalid_value(
+                                ::humanbyte::serde::de::Unexpected::Unsigned(value),
+                                &"integer overflow",
+                            ))
+                        }
+                    }
+
+                    fn visit_u128<E: ::humanbyte::serde::de::Error>(self, value: u128) -> core::result::Result<Self::Value, E> {
+                        if let Ok(val) = #inner::try_from(value) {
+                            Ok(#construct)
+                        } else {
+                            Err(E::invalid_value(
+                                ::humanbyte::serde::de::Unexpected::Other("u128"),
+                                &"integer overflow",
+                            ))
+                        }
                     }
 
                     fn visit_str<E: ::humanbyte::serde::de::Error>(self, value: &str) -> core::result::Result<Self::Value, E> {
@@ -393,26 +1028,66 @@ pub fn humanbyte_serde(input: TokenStream) -> TokenStream {
                     }
                 }
 
+                let visitor = ByteSizeVistor(core::marker::PhantomData);
                 if deserializer.is_human_readable() {
-                    deserializer.deserialize_any(ByteSizeVistor)
+                    deserializer.deserialize_any(visitor)
                 } else {
-                    deserializer.deserialize_u64(ByteSizeVistor)
+                    deserializer.#deserialize_inner(visitor)
                 }
             }
         }
-        impl ::humanbyte::serde::Serialize for #name {
+        impl #impl_generics ::humanbyte::serde::Serialize for #name #ty_generics #where_clause {
             fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
             where
                 S: ::humanbyte::serde::Serializer,
             {
                 if serializer.is_human_readable() {
-                    <str>::serialize(self.to_string().as_str(), serializer)
+                    <str>::serialize(#serialize_human_readable.as_str(), serializer)
                 } else {
-                    self.0.serialize(serializer)
+                    self.#member.serialize(serializer)
                 }
             }
         }
-    };
+    }
+}
 
-    TokenStream::from(expanded)
+/// Parses a byte size at compile time, with the grammar of `FromStr` under the standard convention.
+///
+/// `bytes!("4.5 MiB")` evaluates to `4718592` as the unsigned integer type expected at the call site,
+/// and `bytes!(ByteSize, "4.5 MiB")` to `ByteSize::b(4718592)`, either of which can initialize a
+/// `const`. The string is parsed by `humanbyte::parse_bytes_u128` during constant evaluation, so
+/// invalid sizes fail to compile, as do sizes that do not fit into the integer type or the inner
+/// type of `ByteSize`. The standard convention applies to the typed form as well, regardless of a
+/// `#[humanbyte(convention = ...)]` on `ByteSize`:
+///
+/// ```compile_fail
+/// const LIMIT: u64 = humanbyte::bytes!("4.5 MiB/s");
+/// ```
+///
+/// ```compile_fail
+/// #![allow(overflowing_literals)]
+/// const LIMIT: u32 = humanbyte::bytes!("8 GiB");
+/// ```
+#[proc_macro]
+pub fn bytes(input: TokenStream) -> TokenStream {
+    let literal::BytesInput { ty, literal } = parse_macro_input!(input as literal::BytesInput);
+    let target = match &ty {
+        Some(ty) => quote!(<#ty as ::humanbyte::ByteNewType>::Inner),
+        None => quote!(_),
+    };
+    // errors of the constant evaluation are reported at the string
+    let bytes = quote_spanned! {literal.span()=>
+        {
+            const BYTES: u128 = match ::humanbyte::parse_bytes_u128(#literal) {
+                ::core::result::Result::Ok(bytes) => bytes,
+                ::core::result::Result::Err(kind) => ::core::panic!("{}", kind.as_str()),
+            };
+            <::humanbyte::__private::Bytes<BYTES> as ::humanbyte::__private::Narrow<#target>>::VALUE
+        }
+    };
+    match ty {
+        Some(ty) => quote!(<#ty>::b(#bytes)),
+        None => bytes,
+    }
+    .into()
 }
@@ -0,0 +1,27 @@
+//! The input of the `bytes!` macro. The string itself is parsed by `humanbyte::parse_bytes_u128`
+//! during constant evaluation, so that the grammar is not duplicated here.
+
+use syn::parse::{Parse, ParseStream};
+
+/// The input of `bytes!`: a string literal, optionally preceded by the type to construct.
+pub(crate) struct BytesInput {
+    pub(crate) ty: Option<syn::Type>,
+    pub(crate) literal: syn::LitStr,
+}
+
+impl Parse for BytesInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitStr) {
+            return Ok(BytesInput {
+                ty: None,
+                literal: input.parse()?,
+            });
+        }
+        let ty = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        Ok(BytesInput {
+            ty: Some(ty),
+            literal: input.parse()?,
+        })
+    }
+}
@@ -0,0 +1,75 @@
+//! Compares formatting sizes with integer arithmetic against the former float implementation, which
+//! picked the unit with `f64::ln`.
+//!
+//! Run with `cargo bench -p humanbyte`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use humanbyte::{Format, FormattedSize, KIB};
+
+/// `ln(1024) ~= 6.931`
+const LN_KIB: f64 = 6.931_471_805_599_453;
+
+const UNITS_IEC: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// The float formatting `humanbyte::to_string` used before, for IEC units.
+fn to_string_float(bytes: u64) -> String {
+    if bytes < KIB {
+        format!("{} B", bytes)
+    } else {
+        let size = bytes as f64;
+        let exp = match (size.ln() / LN_KIB) as usize {
+            0 => 1,
+            e => e,
+        };
+        format!(
+            "{:.1} {}",
+            size / KIB.pow(exp as u32) as f64,
+            UNITS_IEC[exp]
+        )
+    }
+}
+
+/// Returns `count` sizes spread over all units, from a fixed seed so that runs are comparable.
+fn random_sizes(count: usize) -> Vec<u64> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..count)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state >> (state % 64)
+        })
+        .collect()
+}
+
+fn format(c: &mut Criterion) {
+    let sizes = random_sizes(1_000_000);
+    let mut group = c.benchmark_group("format a million sizes");
+    group.sample_size(10);
+    group.bench_function("float", |b| {
+        b.iter(|| {
+            for &size in &sizes {
+                black_box(to_string_float(black_box(size)));
+            }
+        })
+    });
+    group.bench_function("integer", |b| {
+        b.iter(|| {
+            for &size in &sizes {
+                black_box(humanbyte::to_string(black_box(size), Format::IEC));
+            }
+        })
+    });
+    group.bench_function("integer on the stack", |b| {
+        b.iter(|| {
+            for &size in &sizes {
+                black_box(FormattedSize::new(black_box(size), Format::IEC, 1));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, format);
+criterion_main!(benches);
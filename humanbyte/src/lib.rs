@@ -1,7 +1,7 @@
 #![no_std]
-#![cfg_attr(not(feature = "std"), no_std)]
 
 //! Common types and functions for byte size handling
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 #[cfg(feature = "std")]
@@ -11,9 +11,11 @@ extern crate std;
 pub use serde;
 
 // Re-export necessary types to avoid users needing explicit extern crate declarations
+#[cfg(feature = "alloc")]
 pub use alloc::{
     format,
     string::{String, ToString},
+    vec::Vec,
 };
 pub use core::str::FromStr;
 
@@ -32,6 +34,16 @@ pub const GB: u64 = 1_000_000_000;
 pub const TB: u64 = 1_000_000_000_000;
 /// bytes size for 1 petabyte
 pub const PB: u64 = 1_000_000_000_000_000;
+/// bytes size for 1 exabyte
+pub const EB: u64 = 1_000_000_000_000_000_000;
+/// bytes size for 1 zettabyte, which only fits into `u128`
+pub const ZB: u128 = 1_000_000_000_000_000_000_000;
+/// bytes size for 1 yottabyte, which only fits into `u128`
+pub const YB: u128 = 1_000_000_000_000_000_000_000_000;
+/// bytes size for 1 ronnabyte, which only fits into `u128`
+pub const RB: u128 = 1_000_000_000_000_000_000_000_000_000;
+/// bytes size for 1 quettabyte, which only fits into `u128`
+pub const QB: u128 = 1_000_000_000_000_000_000_000_000_000_000;
 
 /// bytes size for 1 kibibyte
 pub const KIB: u64 = 1_024;
@@ -43,66 +55,748 @@ pub const GIB: u64 = 1_073_741_824;
 pub const TIB: u64 = 1_099_511_627_776;
 /// bytes size for 1 pebibyte
 pub const PIB: u64 = 1_125_899_906_842_624;
+/// bytes size for 1 exbibyte
+pub const EIB: u64 = 1_152_921_504_606_846_976;
+/// bytes size for 1 zebibyte, which only fits into `u128`
+pub const ZIB: u128 = 1 << 70;
+/// bytes size for 1 yobibyte, which only fits into `u128`
+pub const YIB: u128 = 1 << 80;
+/// bytes size for 1 robibyte (binary ronnabyte, not yet standardized by the IEC), which only fits
+/// into `u128`
+pub const RIB: u128 = 1 << 90;
+/// bytes size for 1 quebibyte (binary quettabyte, not yet standardized by the IEC), which only fits
+/// into `u128`
+pub const QIB: u128 = 1 << 100;
 
-/// IEC (binary) units.
+/// Unsigned integer types that can back a `HumanByte` new type.
 ///
-/// See <https://en.wikipedia.org/wiki/Kilobyte>.
-const UNITS_IEC: &str = "KMGTPE";
-/// SI (decimal) units.
-///
-/// See <https://en.wikipedia.org/wiki/Kilobyte>.
-const UNITS_SI: &str = "kMGTPE";
-/// `ln(1024) ~= 6.931`
-const LN_KIB: f64 = 6.931_471_805_599_453;
-/// `ln(1000) ~= 6.908`
-const LN_KB: f64 = 6.907_755_278_982_137;
+/// Formatting and parsing widen every value to `u128`, so this trait only needs to describe how to
+/// move between the inner type and `u128` without losing information.
+pub trait ByteInt: Copy + Ord + Default + core::fmt::Debug + core::fmt::Display + 'static {
+    /// Zero bytes.
+    const ZERO: Self;
+    /// The largest value representable by the integer type.
+    const MAX: Self;
+
+    /// Widens the value to a `u128`.
+    fn into_u128(self) -> u128;
+
+    /// Narrows a `u128` into the integer type, returning `None` if it does not fit.
+    fn from_u128(value: u128) -> Option<Self>;
+}
+
+macro_rules! impl_byte_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ByteInt for $ty {
+                const ZERO: Self = 0;
+                const MAX: Self = <$ty>::MAX;
+
+                #[inline(always)]
+                fn into_u128(self) -> u128 {
+                    self as u128
+                }
+
+                #[inline(always)]
+                fn from_u128(value: u128) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_int!(u8, u16, u32, u64, u128, usize);
+
+/// Implemented by new types deriving `HumanByteConstructor`, giving generic code access to the
+/// inner integer.
+pub trait ByteNewType: Sized {
+    /// The unsigned integer carried by the new type.
+    type Inner: ByteInt;
+
+    /// Wraps an inner integer.
+    fn from_inner(inner: Self::Inner) -> Self;
+
+    /// Returns the inner integer.
+    fn inner(&self) -> Self::Inner;
+}
+
 #[derive(Debug, Clone, Default)]
 pub enum Format {
     #[default]
     IEC,
     SI,
+    /// SI (decimal) units of bits, e.g. `99.2 Mbit`, as is common for network speeds.
+    SIBits,
+    /// IEC (binary) units of bits, e.g. `94.6 Mibit`.
+    IECBits,
+    /// IEC (binary) units with English long names, e.g. `1.5 kibibytes` or `1 byte`, see
+    /// [`LongNames`].
+    IECLong,
+    /// SI (decimal) units with English long names, e.g. `1.5 kilobytes` or `1 byte`, see
+    /// [`LongNames`].
+    SILong,
+}
+
+impl Format {
+    /// Whether the format uses binary prefixes.
+    fn binary(&self) -> bool {
+        matches!(self, Format::IEC | Format::IECBits | Format::IECLong)
+    }
+
+    /// Whether the format counts bits rather than bytes.
+    fn bits(&self) -> bool {
+        matches!(self, Format::SIBits | Format::IECBits)
+    }
+
+    /// Returns the names the format uses for units.
+    fn names(&self) -> &'static dyn UnitNames {
+        match self {
+            Format::IECLong | Format::SILong => &LongNames,
+            _ => &Symbols,
+        }
+    }
+}
+
+/// Names of units used when formatting sizes, see [`to_string_with_names`].
+///
+/// Implement this to supply translated unit names, e.g. French `octets` and `Ko`.
+pub trait UnitNames {
+    /// Returns the name of `unit` following `number`, the formatted amount such as `"1"` or `"1.5"`,
+    /// which allows choosing between singular and plural forms.
+    fn name(&self, unit: Unit, number: &str) -> &str;
+}
+
+/// The canonical unit symbols, e.g. `KiB` or `Mbit`, see [`Unit::symbol`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Symbols;
+
+impl UnitNames for Symbols {
+    fn name(&self, unit: Unit, _number: &str) -> &str {
+        unit.symbol()
+    }
+}
+
+/// English unit names, e.g. `kibibytes` or `megabit`.
+///
+/// Following the CLDR plural rules for English, only an amount of exactly `1` is singular, so sizes
+/// are shown as `1 byte`, `2 bytes` and `1.0 kibibytes`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LongNames;
+
+impl LongNames {
+    /// Returns the plural name of `unit`, the singular being the same without the trailing `s`.
+    const fn plural(unit: Unit) -> &'static str {
+        match unit {
+            Unit::Byte => "bytes",
+            // power of tens
+            Unit::KiloByte => "kilobytes",
+            Unit::MegaByte => "megabytes",
+            Unit::GigaByte => "gigabytes",
+            Unit::TeraByte => "terabytes",
+            Unit::PetaByte => "petabytes",
+            Unit::ExaByte => "exabytes",
+            Unit::ZettaByte => "zettabytes",
+            Unit::YottaByte => "yottabytes",
+            Unit::RonnaByte => "ronnabytes",
+            Unit::QuettaByte => "quettabytes",
+            // power of twos
+            Unit::KibiByte => "kibibytes",
+            Unit::MebiByte => "mebibytes",
+            Unit::GibiByte => "gibibytes",
+            Unit::TebiByte => "tebibytes",
+            Unit::PebiByte => "pebibytes",
+            Unit::ExbiByte => "exbibytes",
+            Unit::ZebiByte => "zebibytes",
+            Unit::YobiByte => "yobibytes",
+            Unit::RobiByte => "robibytes",
+            Unit::QuebiByte => "quebibytes",
+            Unit::Bit => "bits",
+            // power of tens
+            Unit::KiloBit => "kilobits",
+            Unit::MegaBit => "megabits",
+            Unit::GigaBit => "gigabits",
+            Unit::TeraBit => "terabits",
+            Unit::PetaBit => "petabits",
+            Unit::ExaBit => "exabits",
+            Unit::ZettaBit => "zettabits",
+            Unit::YottaBit => "yottabits",
+            Unit::RonnaBit => "ronnabits",
+            Unit::QuettaBit => "quettabits",
+            // power of twos
+            Unit::KibiBit => "kibibits",
+            Unit::MebiBit => "mebibits",
+            Unit::GibiBit => "gibibits",
+            Unit::TebiBit => "tebibits",
+            Unit::PebiBit => "pebibits",
+            Unit::ExbiBit => "exbibits",
+            Unit::ZebiBit => "zebibits",
+            Unit::YobiBit => "yobibits",
+            Unit::RobiBit => "robibits",
+            Unit::QuebiBit => "quebibits",
+        }
+    }
+}
+
+impl UnitNames for LongNames {
+    fn name(&self, unit: Unit, number: &str) -> &str {
+        let plural = Self::plural(unit);
+        if number == "1" {
+            &plural[..plural.len() - 1]
+        } else {
+            plural
+        }
+    }
+}
+
+/// How numbers are written: the decimal separator and the optional thousands separator.
+///
+/// Used by [`to_string_with_style`] and, opt-in, by [`Parser::number_style`]. When parsing, grouped
+/// digits must come in groups of three, so `"1,5"` is rejected rather than read as 15 when `,`
+/// separates thousands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumberStyle {
+    decimal_separator: char,
+    group_separator: Option<char>,
+}
+
+impl Default for NumberStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NumberStyle {
+    /// Creates the default style with a `.` decimal separator and no grouping, e.g. `1234.5`.
+    pub const fn new() -> Self {
+        NumberStyle {
+            decimal_separator: '.',
+            group_separator: None,
+        }
+    }
+
+    /// Sets the decimal separator, e.g. `,` for `1,5`.
+    pub const fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Sets the separator between groups of three digits, e.g. `,`, `_`, `'` or a thin space
+    /// (`'\u{2009}'`), or `None` to not group digits.
+    pub const fn group_separator(mut self, separator: Option<char>) -> Self {
+        self.group_separator = separator;
+        self
+    }
+
+    /// Writes `number`, formatted with a `.` decimal separator and no grouping, in this style.
+    fn write<W: core::fmt::Write + ?Sized>(&self, out: &mut W, number: &str) -> core::fmt::Result {
+        let (integer, fraction) = match number.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (number, None),
+        };
+        for (index, digit) in integer.chars().enumerate() {
+            let remaining = integer.len() - index;
+            if let Some(separator) = self.group_separator {
+                if index != 0 && remaining % 3 == 0 {
+                    out.write_char(separator)?;
+                }
+            }
+            out.write_char(digit)?;
+        }
+        if let Some(fraction) = fraction {
+            out.write_char(self.decimal_separator)?;
+            out.write_str(fraction)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the end of the number starting at `start`, and its integer and fraction digits,
+    /// which may include group separators.
+    fn scan<'a>(&self, value: &'a str, start: usize) -> Result<(&'a str, &'a str), ParseError> {
+        let rest = &value[start..];
+        let mut integer_end = 0;
+        let mut group_len = 0;
+        let mut grouped = false;
+        let mut chars = rest.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            let next_is_digit = matches!(chars.peek(), Some((_, next)) if next.is_ascii_digit());
+            if c.is_ascii_digit() {
+                group_len += 1;
+            } else if Some(c) == self.group_separator && next_is_digit {
+                if !(1..=3).contains(&group_len) || (grouped && group_len != 3) {
+                    return Err(ParseError::new(
+                        ParseErrorKind::InvalidNumber,
+                        start..start + index,
+                    ));
+                }
+                grouped = true;
+                group_len = 0;
+            } else {
+                break;
+            }
+            integer_end = index + c.len_utf8();
+        }
+        if grouped && group_len != 3 {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidNumber,
+                start..start + integer_end,
+            ));
+        }
+
+        let integer = &rest[..integer_end];
+        let fraction = match rest[integer_end..].strip_prefix(self.decimal_separator) {
+            Some(fraction) => take_while(fraction, |c| c.is_ascii_digit()),
+            None => "",
+        };
+        Ok((integer, fraction))
+    }
+}
+
+/// The largest number of decimal places sizes are formatted with; larger precisions are reduced to
+/// it.
+pub const MAX_PRECISION: usize = 64;
+
+/// Room for the 39 digits of `u128::MAX`, or a decimal point and [`MAX_PRECISION`] decimal places.
+const NUMBER_CAPACITY: usize = 40 + MAX_PRECISION;
+
+/// Room for a sign, a number, a space, the longest unit name and `/s`.
+const FORMATTED_CAPACITY: usize = 128;
+
+/// A string of up to `N` bytes on the stack, whose writes fail once it is full.
+#[derive(Clone, Copy)]
+struct Buffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Buffer<N> {
+    const fn new() -> Self {
+        Buffer {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // only whole strings are written, so the bytes are always valid UTF-8
+        core::str::from_utf8(&self.bytes[..self.len]).expect("buffer holds whole strings")
+    }
+}
+
+impl<const N: usize> core::fmt::Write for Buffer<N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        if end > N {
+            return Err(core::fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// A size formatted into a buffer on the stack, for displaying sizes without an allocator.
+///
+/// This is what the derived `Display` implementations use. It always uses the unit names of the
+/// format and the default [`NumberStyle`], so the result is bounded and always fits.
+#[derive(Clone, Copy)]
+pub struct FormattedSize(Buffer<FORMATTED_CAPACITY>);
+
+impl FormattedSize {
+    /// Formats `bytes` with `precision` decimal places, like [`to_string_with_precision`].
+    pub fn new<T: ByteInt>(bytes: T, format: Format, precision: usize) -> Self {
+        Self::build(|buffer| write_to(buffer, bytes, format, precision))
+    }
+
+    /// Formats `bytes` so that it parses back to exactly the same size, like [`to_string_exact`].
+    pub fn exact<T: ByteInt>(bytes: T, format: Format) -> Self {
+        Self::build(|buffer| write_exact(buffer, bytes, format))
+    }
+
+    /// Formats a rate in bytes per second, like [`rate_to_string_with_precision`].
+    pub fn rate<T: ByteInt>(bytes_per_second: T, format: Format, precision: usize) -> Self {
+        use core::fmt::Write;
+
+        Self::build(|buffer| {
+            write_to(buffer, bytes_per_second, format, precision)?;
+            buffer.write_str("/s")
+        })
+    }
+
+    /// Returns the formatted size.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    fn build(write: impl FnOnce(&mut Buffer<FORMATTED_CAPACITY>) -> core::fmt::Result) -> Self {
+        let mut buffer = Buffer::new();
+        write(&mut buffer).expect("formatted size fits its buffer");
+        FormattedSize(buffer)
+    }
+}
+
+impl AsRef<str> for FormattedSize {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Honors width, fill and alignment, see [`pad`].
+impl core::fmt::Display for FormattedSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        pad(f, self.as_str())
+    }
+}
+
+impl core::fmt::Debug for FormattedSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub fn to_string<T: ByteInt>(bytes: T, format: Format) -> String {
+    to_string_with_precision(bytes, format, 1)
+}
+
+/// Like [`to_string`], but with `precision` decimal places instead of one, up to
+/// [`MAX_PRECISION`]. The last decimal place is rounded half up.
+///
+/// Sizes below one kilobyte (or kibibyte) are always shown as a whole number of bytes, or bits for
+/// the bit formats.
+#[cfg(feature = "alloc")]
+pub fn to_string_with_precision<T: ByteInt>(bytes: T, format: Format, precision: usize) -> String {
+    FormattedSize::new(bytes, format, precision).as_str().into()
+}
+
+/// Like [`to_string_with_precision`], but naming units with `names` instead of the names of
+/// `format`, e.g. to translate them.
+#[cfg(feature = "alloc")]
+pub fn to_string_with_names<T: ByteInt, N: UnitNames + ?Sized>(
+    bytes: T,
+    format: Format,
+    precision: usize,
+    names: &N,
+) -> String {
+    to_string_with_style(bytes, format, precision, &NumberStyle::new(), names)
+}
+
+/// Like [`to_string_with_names`], but writing the number in `style`, e.g. `1,5 KiB`.
+///
+/// Pass [`Symbols`] or [`LongNames`] as `names` for the built-in unit names. They are given the
+/// number in the default style, so that plural rules need not know about separators.
+#[cfg(feature = "alloc")]
+pub fn to_string_with_style<T: ByteInt, N: UnitNames + ?Sized>(
+    bytes: T,
+    format: Format,
+    precision: usize,
+    style: &NumberStyle,
+    names: &N,
+) -> String {
+    let mut string = String::new();
+    write_with_style(&mut string, bytes, format, precision, style, names)
+        .expect("writing to a String cannot fail");
+    string
+}
+
+/// Writes the size to `out` like [`to_string_with_precision`], without allocating.
+pub fn write_to<W: core::fmt::Write + ?Sized, T: ByteInt>(
+    out: &mut W,
+    bytes: T,
+    format: Format,
+    precision: usize,
+) -> core::fmt::Result {
+    let names = format.names();
+    write_with_style(out, bytes, format, precision, &NumberStyle::new(), names)
+}
+
+/// Writes the size to `out` like [`to_string_with_style`], without allocating.
+pub fn write_with_style<W, T, N>(
+    out: &mut W,
+    bytes: T,
+    format: Format,
+    precision: usize,
+    style: &NumberStyle,
+    names: &N,
+) -> core::fmt::Result
+where
+    W: core::fmt::Write + ?Sized,
+    T: ByteInt,
+    N: UnitNames + ?Sized,
+{
+    use core::fmt::Write;
+
+    let bytes = bytes.into_u128();
+    let unit = if format.binary() { KIB } else { KB } as u128;
+    let units = Unit::table(format.binary(), format.bits());
+    let per_byte = if format.bits() { 8 } else { 1 };
+    let mut number = Buffer::<NUMBER_CAPACITY>::new();
+    let exp = if bytes < unit / per_byte {
+        write!(number, "{}", bytes * per_byte)?;
+        0
+    } else {
+        // values beyond the largest unit are shown as a multiple of that unit
+        let mut exp = 1;
+        while exp < units.len() - 1 && bytes >= unit.pow(exp as u32 + 1) / per_byte {
+            exp += 1;
+        }
+        // powers of 1000 and 1024 are multiples of 8, so the unit divides exactly into bits
+        let divisor = unit.pow(exp as u32) / per_byte;
+        write_decimal(&mut number, bytes, divisor, precision.min(MAX_PRECISION))?;
+        exp
+    };
+    style.write(out, number.as_str())?;
+    out.write_char(' ')?;
+    out.write_str(names.name(units[exp], number.as_str()))
+}
+
+/// Writes `dividend / divisor` rounded half up to `precision` decimal places, using only integer
+/// arithmetic so that no float formatting or `libm` is needed.
+///
+/// `divisor` must not exceed `2^124`, and `precision` must not exceed [`MAX_PRECISION`].
+fn write_decimal<W: core::fmt::Write + ?Sized>(
+    out: &mut W,
+    dividend: u128,
+    divisor: u128,
+    precision: usize,
+) -> core::fmt::Result {
+    let mut integer = dividend / divisor;
+    let mut remainder = dividend % divisor;
+    let mut digits = [b'0'; MAX_PRECISION];
+    let digits = &mut digits[..precision];
+    for digit in digits.iter_mut() {
+        remainder *= 10;
+        *digit = b'0' + (remainder / divisor) as u8;
+        remainder %= divisor;
+    }
+    // round half up, carrying past trailing nines into the integer part
+    if remainder >= divisor - remainder {
+        match digits.iter().rposition(|&digit| digit != b'9') {
+            Some(index) => {
+                digits[index] += 1;
+                digits[index + 1..].fill(b'0');
+            }
+            None => {
+                digits.fill(b'0');
+                integer += 1;
+            }
+        }
+    }
+    write!(out, "{}", integer)?;
+    if !digits.is_empty() {
+        out.write_char('.')?;
+        out.write_str(core::str::from_utf8(digits).expect("digits are ASCII"))?;
+    }
+    Ok(())
+}
+
+/// Formats a rate in bytes per second, e.g. `"12.4 MiB/s"`.
+#[cfg(feature = "alloc")]
+pub fn rate_to_string_with_precision<T: ByteInt>(
+    bytes_per_second: T,
+    format: Format,
+    precision: usize,
+) -> String {
+    FormattedSize::rate(bytes_per_second, format, precision)
+        .as_str()
+        .into()
+}
+
+/// Formats a rate in bits per second, as is common for network speeds, e.g. `"99.2 Mbit/s"`.
+///
+/// Byte formats are replaced by the bit format with the same prefixes.
+#[cfg(feature = "alloc")]
+pub fn rate_to_bits_string_with_precision<T: ByteInt>(
+    bytes_per_second: T,
+    format: Format,
+    precision: usize,
+) -> String {
+    let format = if format.binary() {
+        Format::IECBits
+    } else {
+        Format::SIBits
+    };
+    rate_to_string_with_precision(bytes_per_second, format, precision)
 }
 
-pub fn to_string(bytes: u64, format: Format) -> String {
-    let unit = match format {
-        Format::IEC => KIB,
-        Format::SI => KB,
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Returns the bytes per second when transferring `bytes` in `duration`, rounded down.
+///
+/// Returns `None` if the rate does not fit into a `u128`. Panics if `duration` is zero.
+pub fn transfer_rate(bytes: u128, duration: core::time::Duration) -> Option<u128> {
+    let nanos = duration.as_nanos();
+    assert!(nanos != 0, "transfer rate over a zero duration");
+    match bytes.checked_mul(NANOS_PER_SEC) {
+        Some(bytes) => Some(bytes / nanos),
+        None => (bytes / nanos).checked_mul(NANOS_PER_SEC),
+    }
+}
+
+/// Returns the bytes transferred in `duration` at `bytes_per_second`, rounded down.
+///
+/// Returns `None` if the size does not fit into a `u128`.
+pub fn transferred(bytes_per_second: u128, duration: core::time::Duration) -> Option<u128> {
+    let seconds = bytes_per_second.checked_mul(duration.as_secs() as u128)?;
+    let nanos = duration.subsec_nanos() as u128;
+    let fraction = match bytes_per_second.checked_mul(nanos) {
+        Some(bytes) => bytes / NANOS_PER_SEC,
+        None => bytes_per_second / NANOS_PER_SEC * nanos,
     };
-    let unit_base = match format {
-        Format::IEC => LN_KIB,
-        Format::SI => LN_KB,
+    seconds.checked_add(fraction)
+}
+
+/// Returns the size as a string that parses back to exactly `bytes`.
+///
+/// Uses the largest unit that divides `bytes` without remainder, e.g. `"3 MiB"`, and falls back to
+/// plain bytes, e.g. `"1536 B"`, when no unit does.
+///
+/// Bit formats count in bits, e.g. `"8 Mbit"`, unless the number of bits does not fit into a `u128`.
+#[cfg(feature = "alloc")]
+pub fn to_string_exact<T: ByteInt>(bytes: T, format: Format) -> String {
+    FormattedSize::exact(bytes, format).as_str().into()
+}
+
+/// Writes the size to `out` like [`to_string_exact`], without allocating.
+pub fn write_exact<W: core::fmt::Write + ?Sized, T: ByteInt>(
+    out: &mut W,
+    bytes: T,
+    format: Format,
+) -> core::fmt::Result {
+    use core::fmt::Write;
+
+    let unit = if format.binary() { KIB } else { KB } as u128;
+    let bits = bytes.into_u128().checked_mul(8).filter(|_| format.bits());
+    let (mut size, units) = match bits {
+        Some(bits) => (bits, Unit::table(format.binary(), true)),
+        None => (bytes.into_u128(), Unit::table(format.binary(), false)),
     };
-    let unit_prefix = match format {
-        Format::IEC => UNITS_IEC.as_bytes(),
-        Format::SI => UNITS_SI.as_bytes(),
+    let mut exp = 0;
+    while size != 0 && exp < units.len() - 1 {
+        let (quotient, remainder) = (size / unit, size % unit);
+        if remainder != 0 {
+            break;
+        }
+        size = quotient;
+        exp += 1;
+    }
+    let mut number = Buffer::<NUMBER_CAPACITY>::new();
+    write!(number, "{}", size)?;
+    let name = format.names().name(units[exp], number.as_str());
+    write!(out, "{} {}", number.as_str(), name)
+}
+
+/// Writes `s` honoring the width, fill and alignment of `f`.
+///
+/// Unlike [`core::fmt::Formatter::pad`], the precision of `f` is ignored rather than used to truncate
+/// `s`, as `HumanByte` types use it to choose the number of decimal places.
+pub fn pad(f: &mut core::fmt::Formatter<'_>, s: &str) -> core::fmt::Result {
+    use core::fmt::{Alignment, Write};
+
+    let len = s.chars().count();
+    let padding = match f.width() {
+        Some(width) if width > len => width - len,
+        _ => return f.write_str(s),
     };
-    let unit_suffix = match format {
-        Format::IEC => "iB",
-        Format::SI => "B",
+    let (pre, post) = match f.align() {
+        None | Some(Alignment::Left) => (0, padding),
+        Some(Alignment::Right) => (padding, 0),
+        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
     };
-    if bytes < unit {
-        format!("{} B", bytes)
-    } else {
-        let size = bytes as f64;
-        let exp = match (size.ln() / unit_base) as usize {
-            0 => 1,
-            e => e,
-        };
-        format!(
-            "{:.1} {}{}",
-            (size / unit.pow(exp as u32) as f64),
-            unit_prefix[exp - 1] as char,
-            unit_suffix
-        )
+    let fill = f.fill();
+    for _ in 0..pre {
+        f.write_char(fill)?;
+    }
+    f.write_str(s)?;
+    for _ in 0..post {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// The reason a byte size could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// The input is empty or only whitespace.
+    Empty,
+    /// The input does not start with a number.
+    InvalidNumber,
+    /// The unit following the number is not recognized.
+    UnknownUnit,
+    /// The unit is recognized but not allowed by the [`Parser`], e.g. a non-canonical symbol.
+    DisallowedUnit,
+    /// The value does not fit into the target integer type.
+    Overflow,
+    /// The number is negative.
+    Negative,
+    /// Unexpected characters follow the unit.
+    TrailingGarbage,
+    /// The input is not a range such as `1 MiB..4 GiB` or `>=500 MB`.
+    InvalidRange,
+}
+
+impl ParseErrorKind {
+    /// Returns the description of the error, e.g. `"unknown unit"`, which is also usable in `const`
+    /// panics.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ParseErrorKind::Empty => "empty input",
+            ParseErrorKind::InvalidNumber => "invalid number",
+            ParseErrorKind::UnknownUnit => "unknown unit",
+            ParseErrorKind::DisallowedUnit => "unit not allowed",
+            ParseErrorKind::Overflow => "value overflows",
+            ParseErrorKind::Negative => "negative values are not allowed",
+            ParseErrorKind::TrailingGarbage => "unexpected trailing characters",
+            ParseErrorKind::InvalidRange => "invalid range",
+        }
+    }
+}
+
+impl core::fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
-#[derive(Debug)]
-pub struct ParseError(pub String);
+/// Error returned when parsing a byte size or [`Unit`] fails.
+///
+/// Carries the [`ParseErrorKind`] and the byte range of the offending characters in the input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    span: core::ops::Range<usize>,
+}
+
+impl ParseError {
+    /// Creates an error of `kind` covering the bytes `span` of the input.
+    pub fn new(kind: ParseErrorKind, span: core::ops::Range<usize>) -> Self {
+        ParseError { kind, span }
+    }
+
+    /// Returns the reason parsing failed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte range of the offending characters in the input.
+    pub fn span(&self) -> core::ops::Range<usize> {
+        self.span.clone()
+    }
+
+    /// Moves the span by `offset` bytes, for errors from parsing a slice of a larger input.
+    fn offset(mut self, offset: usize) -> Self {
+        self.span = self.span.start + offset..self.span.end + offset;
+        self
+    }
+}
 
 impl core::fmt::Display for ParseError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(
+            f,
+            "{} at bytes {}..{}",
+            self.kind, self.span.start, self.span.end
+        )
     }
 }
 
@@ -133,85 +827,1639 @@ where
     &s[(s.len() - offset)..]
 }
 
-pub enum Unit {
-    Byte,
-    // power of tens
-    KiloByte,
-    MegaByte,
-    GigaByte,
-    TeraByte,
-    PetaByte,
-    // power of twos
-    KibiByte,
-    MebiByte,
-    GibiByte,
-    TebiByte,
-    PebiByte,
+/// Parses a byte size such as `"1.5 MiB"`, `"512kb"` or `"1024"` into an integer of type `T`.
+///
+/// The number is parsed exactly using integer arithmetic, so arbitrarily many decimal places are
+/// honored. Fractions of a byte are truncated towards zero, e.g. `"1.9999 KiB"` is 2047 bytes and
+/// `"1.5"` is 1 byte. A missing unit means bytes. Values that do not fit into `T` are rejected
+/// rather than saturated.
+///
+/// Errors carry the byte range of the offending characters in `value`.
+pub fn parse<T: ByteInt>(value: &str) -> Result<T, ParseError> {
+    Parser::new().parse(value)
 }
 
-impl From<Unit> for u64 {
-    fn from(unit: Unit) -> u64 {
-        match unit {
-            Unit::Byte => B,
-            // power of tens
-            Unit::KiloByte => KB,
-            Unit::MegaByte => MB,
-            Unit::GigaByte => GB,
-            Unit::TeraByte => TB,
-            Unit::PetaByte => PB,
-            // power of twos
-            Unit::KibiByte => KIB,
-            Unit::MebiByte => MIB,
-            Unit::GibiByte => GIB,
-            Unit::TebiByte => TIB,
-            Unit::PebiByte => PIB,
-        }
+/// Like [`parse`], but reads units case-sensitively, see [`Unit::from_str_case_sensitive`].
+///
+/// This tells bits and bytes apart, e.g. `"100 Mb"` is 12.5 megabytes while `"100 MB"` is 100.
+pub fn parse_case_sensitive<T: ByteInt>(value: &str) -> Result<T, ParseError> {
+    Parser::new().case_sensitive(true).parse(value)
+}
+
+/// How unit symbols map to sizes, see [`Parser::convention`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Convention {
+    /// SI prefixes are decimal and IEC prefixes binary, e.g. `kB` and `K` are 1000 bytes while `KiB`
+    /// and `Ki` are 1024. Units are case-insensitive unless [`Parser::case_sensitive`] is set.
+    #[default]
+    Standard,
+    /// JEDEC memory sizes, where SI prefixes are binary as well, e.g. `KB` and `K` are 1024 bytes.
+    /// Units are case-insensitive, so Java heap sizes like `1g` are a gibibyte.
+    Jedec,
+    /// Kubernetes resource quantities, which take decimal suffixes `k`, `M`, `G`, `T`, `P` and `E`,
+    /// binary suffixes `Ki` through `Ei`, decimal exponents such as `129e6`, and the fractional
+    /// suffixes `m`, `u` and `n`, whose results are truncated to whole bytes. Suffixes are
+    /// case-sensitive. The canonical symbols written by `Display`, such as `MiB` or `kB`, are accepted
+    /// as well, so that formatted sizes parse back.
+    Kubernetes,
+    /// GNU coreutils, where a bare prefix is binary and a prefix followed by `B` decimal, e.g. `K` and
+    /// `KiB` are 1024 bytes while `KB` is 1000. Prefixes are uppercase, except for kilo which may also
+    /// be written `k`. There are no bit units, and the 512 byte block `b` is not supported.
+    Gnu,
+}
+
+/// A byte size parser with configurable policies for the accepted units.
+///
+/// [`Parser::new`] is as lenient as [`parse`], while [`Parser::strict`] only accepts canonical
+/// symbols, which suits user-facing configuration:
+///
+/// ```
+/// use humanbyte::{ParseErrorKind, Parser};
+///
+/// let parser = Parser::strict().allow_si(false);
+/// assert_eq!(parser.parse::<u64>("2 KiB"), Ok(2048));
+/// assert_eq!(parser.parse::<u64>("2 kib").unwrap_err().kind(), ParseErrorKind::DisallowedUnit);
+/// assert_eq!(parser.parse::<u64>("2 kB").unwrap_err().kind(), ParseErrorKind::DisallowedUnit);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Parser {
+    convention: Convention,
+    number_style: NumberStyle,
+    case_sensitive: bool,
+    canonical: bool,
+    allow_si: bool,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl FromStr for Unit {
-    type Err = String;
+impl Parser {
+    /// Creates a lenient parser accepting units in any case, like [`parse`].
+    pub const fn new() -> Self {
+        Parser {
+            convention: Convention::Standard,
+            number_style: NumberStyle::new(),
+            case_sensitive: false,
+            canonical: false,
+            allow_si: true,
+        }
+    }
 
-    fn from_str(unit: &str) -> Result<Self, Self::Err> {
-        match unit.to_lowercase().as_str() {
-            "b" => Ok(Self::Byte),
-            // power of tens
-            "k" | "kb" => Ok(Self::KiloByte),
-            "m" | "mb" => Ok(Self::MegaByte),
-            "g" | "gb" => Ok(Self::GigaByte),
-            "t" | "tb" => Ok(Self::TeraByte),
-            "p" | "pb" => Ok(Self::PetaByte),
-            // power of twos
-            "ki" | "kib" => Ok(Self::KibiByte),
-            "mi" | "mib" => Ok(Self::MebiByte),
-            "gi" | "gib" => Ok(Self::GibiByte),
-            "ti" | "tib" => Ok(Self::TebiByte),
-            "pi" | "pib" => Ok(Self::PebiByte),
-            _ => Err(format!("couldn't parse unit of {:?}", unit)),
+    /// Creates a parser that only accepts canonical unit symbols, see [`Parser::canonical`].
+    pub const fn strict() -> Self {
+        Self::new().case_sensitive(true).canonical(true)
+    }
+
+    /// Sets how unit symbols map to sizes.
+    ///
+    /// Conventions other than [`Convention::Standard`] fix their own symbols and case sensitivity, so
+    /// [`Parser::case_sensitive`] only applies to the standard convention. [`Parser::canonical`]
+    /// applies to all but [`Convention::Kubernetes`], whose suffixes are canonical already.
+    pub const fn convention(mut self, convention: Convention) -> Self {
+        self.convention = convention;
+        self
+    }
+
+    /// Sets the decimal separator and digit grouping of numbers, e.g. to accept `"1,5 MiB"` or
+    /// `"1 000 B"`. Defaults to [`NumberStyle::new`].
+    pub const fn number_style(mut self, number_style: NumberStyle) -> Self {
+        self.number_style = number_style;
+        self
+    }
+
+    /// Whether units are read case-sensitively, see [`Unit::from_str_case_sensitive`].
+    pub const fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Whether only canonical unit symbols are accepted, see [`Unit::symbol`].
+    ///
+    /// This rejects ambiguous forms such as a bare `k` or `m`, as well as other spellings of known
+    /// units such as `mib` or `KB`, with [`ParseErrorKind::DisallowedUnit`]. [`Convention::Jedec`]
+    /// and [`Convention::Gnu`] also accept kilo written `K`, e.g. `KB`, and the latter a bare
+    /// uppercase prefix such as `K` or `M`.
+    pub const fn canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// Whether units with decimal prefixes, e.g. `kB` or `Mbit`, are accepted. Rejected units fail
+    /// with [`ParseErrorKind::DisallowedUnit`].
+    pub const fn allow_si(mut self, allow_si: bool) -> Self {
+        self.allow_si = allow_si;
+        self
+    }
+
+    /// Parses a unit according to the policies of the parser.
+    ///
+    /// The fractional suffixes and exponents of [`Convention::Kubernetes`] are not units and only
+    /// accepted by [`Parser::parse`].
+    pub fn parse_unit(&self, unit: &str) -> Result<Unit, ParseError> {
+        let unknown = || ParseError::new(ParseErrorKind::UnknownUnit, 0..unit.len());
+        let disallowed = || ParseError::new(ParseErrorKind::DisallowedUnit, 0..unit.len());
+        let parsed = match self.convention {
+            Convention::Standard => {
+                let parsed = if self.case_sensitive {
+                    Unit::from_str_case_sensitive(unit)
+                } else {
+                    unit.parse::<Unit>()
+                };
+                match parsed {
+                    Ok(parsed) if self.canonical && parsed.symbol() != unit => Err(disallowed()),
+                    Err(_) if self.canonical && unit.parse::<Unit>().is_ok() => Err(disallowed()),
+                    parsed => parsed,
+                }
+            }
+            Convention::Jedec => {
+                let parsed = unit.parse::<Unit>()?;
+                if self.canonical && !parsed.is_spelled_by(unit, false) {
+                    return Err(disallowed());
+                }
+                Ok(parsed.to_binary())
+            }
+            Convention::Kubernetes => match unit {
+                "k" => Ok(Unit::KiloByte),
+                "M" => Ok(Unit::MegaByte),
+                "G" => Ok(Unit::GigaByte),
+                "T" => Ok(Unit::TeraByte),
+                "P" => Ok(Unit::PetaByte),
+                "E" => Ok(Unit::ExaByte),
+                "Ki" => Ok(Unit::KibiByte),
+                "Mi" => Ok(Unit::MebiByte),
+                "Gi" => Ok(Unit::GibiByte),
+                "Ti" => Ok(Unit::TebiByte),
+                "Pi" => Ok(Unit::PebiByte),
+                "Ei" => Ok(Unit::ExbiByte),
+                // canonical symbols all end in `B` or `bit`, unlike the suffixes above
+                _ => match Unit::from_str_case_sensitive(unit) {
+                    Ok(parsed) if parsed.symbol() == unit => Ok(parsed),
+                    _ => Err(unknown()),
+                },
+            },
+            Convention::Gnu => {
+                let parsed = Unit::from_str_case_sensitive(unit)?;
+                // coreutils has no bit units, and reads a bare `b` as a 512 byte block
+                if parsed.is_bit() {
+                    return Err(unknown());
+                }
+                if self.canonical && !parsed.is_spelled_by(unit, true) {
+                    return Err(disallowed());
+                }
+                // only a bare prefix is binary
+                if unit.ends_with('B') {
+                    Ok(parsed)
+                } else {
+                    Ok(parsed.to_binary())
+                }
+            }
+        }?;
+        if !self.allow_si && parsed.is_si() {
+            return Err(disallowed());
         }
+        Ok(parsed)
     }
-}
 
-pub struct HumanByteRange<T: From<u64>> {
-    start: T,
-    stop: T,
-}
+    /// Returns the multiplier and divisor converting the number into bytes, and where the unit
+    /// starting at `unit_start` ends.
+    fn scale(&self, value: &str, unit_start: usize) -> Result<(u128, u128, usize), ParseError> {
+        let unit = take_while(&value[unit_start..], char::is_alphabetic);
+        let unit_end = unit_start + unit.len();
+        if self.convention == Convention::Kubernetes {
+            let exponent = take_while(&value[unit_end..], |c| c.is_ascii_digit());
+            match unit {
+                "e" | "E" if !exponent.is_empty() => {
+                    let exponent_end = unit_end + exponent.len();
+                    let multiplier = exponent
+                        .parse::<u32>()
+                        .ok()
+                        .and_then(|exponent| 10u128.checked_pow(exponent))
+                        .ok_or_else(|| {
+                            ParseError::new(ParseErrorKind::Overflow, 0..exponent_end)
+                        })?;
+                    return Ok((multiplier, 1, exponent_end));
+                }
+                "m" => return Ok((1, 1_000, unit_end)),
+                "u" => return Ok((1, 1_000_000, unit_end)),
+                "n" => return Ok((1, 1_000_000_000, unit_end)),
+                _ => {}
+            }
+        }
+        if unit.is_empty() {
+            return Ok((B as u128, 1, unit_end));
+        }
 
-impl<T: From<u64>> HumanByteRange<T> {
-    pub fn new<I: Into<T>>(start: Option<I>, stop: Option<I>) -> Self {
-        HumanByteRange {
-            start: start.map(Into::into).unwrap_or(0.into()),
-            stop: stop.map(Into::into).unwrap_or(u64::MAX.into()),
+        let unit = self
+            .parse_unit(unit)
+            .map_err(|error| error.offset(unit_start))?;
+        // bits are counted as such and converted to bytes at the end, as a single bit is not a whole
+        // number of bytes
+        if unit.is_bit() {
+            Ok((unit.bits(), 8, unit_end))
+        } else {
+            Ok((u128::from(unit), 1, unit_end))
         }
     }
-}
 
-impl<T: From<u64>> core::ops::RangeBounds<T> for HumanByteRange<T> {
+    /// Parses a byte size like [`parse`], applying the policies of the parser to the unit.
+    pub fn parse<T: ByteInt>(&self, value: &str) -> Result<T, ParseError> {
+        if value.chars().all(char::is_whitespace) {
+            return Err(ParseError::new(ParseErrorKind::Empty, 0..value.len()));
+        }
+
+        let (negative, number_start) = match value.as_bytes()[0] {
+            b'-' => (true, 1),
+            b'+' => (false, 1),
+            _ => (false, 0),
+        };
+        let (integer, fraction) = self.number_style.scan(value, number_start)?;
+        let mut number_end = number_start + integer.len();
+        if value[number_end..].starts_with(self.number_style.decimal_separator) {
+            number_end += self.number_style.decimal_separator.len_utf8() + fraction.len();
+        }
+        if integer.is_empty() && fraction.is_empty() {
+            let token = take_while(value, |c| !c.is_whitespace());
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidNumber,
+                0..token.len(),
+            ));
+        }
+        if negative {
+            return Err(ParseError::new(ParseErrorKind::Negative, 0..number_end));
+        }
+
+        let unit_start = value.len() - skip_while(&value[number_end..], char::is_whitespace).len();
+        let (multiplier, divisor, unit_end) = self.scale(value, unit_start)?;
+        let rest = skip_while(&value[unit_end..], char::is_whitespace);
+        if !rest.is_empty() {
+            return Err(ParseError::new(
+                ParseErrorKind::TrailingGarbage,
+                value.len() - rest.len()..value.len(),
+            ));
+        }
+
+        let overflow = || ParseError::new(ParseErrorKind::Overflow, 0..unit_end);
+        let mut bytes: u128 = 0;
+        for digit in integer.bytes().filter(u8::is_ascii_digit) {
+            bytes = bytes
+                .checked_mul(10)
+                .and_then(|bytes| bytes.checked_add((digit - b'0') as u128))
+                .ok_or_else(overflow)?;
+        }
+        bytes = bytes.checked_mul(multiplier).ok_or_else(overflow)?;
+
+        // `floor(0.fraction * multiplier)`, accumulated from the least significant digit so that
+        // intermediate values stay below `multiplier`. Flooring at every step gives the same result
+        // as flooring once. Each step splits `digit * multiplier` into tens and ones, as the product
+        // itself overflows for exponents such as `9e38`.
+        let (tens, ones) = (multiplier / 10, multiplier % 10);
+        let mut fraction_bytes: u128 = 0;
+        for digit in fraction.bytes().rev() {
+            let digit = (digit - b'0') as u128;
+            fraction_bytes = (digit * ones)
+                .checked_add(fraction_bytes)
+                .and_then(|low| (digit * tens).checked_add(low / 10))
+                .ok_or_else(overflow)?;
+        }
+        bytes = bytes.checked_add(fraction_bytes).ok_or_else(overflow)?;
+
+        T::from_u128(bytes / divisor).ok_or_else(overflow)
+    }
+}
+
+/// Parses a byte size like [`parse`] in a `const` context, e.g. to initialize constants.
+///
+/// Accepts the same numbers and units as [`parse`], but only ASCII whitespace. As spans are of little
+/// use at compile time, errors are just the [`ParseErrorKind`].
+pub const fn parse_bytes(value: &str) -> Result<u64, ParseErrorKind> {
+    match parse_bytes_u128(value) {
+        Ok(bytes) if bytes <= u64::MAX as u128 => Ok(bytes as u64),
+        Ok(_) => Err(ParseErrorKind::Overflow),
+        Err(kind) => Err(kind),
+    }
+}
+
+/// Like [`parse_bytes`], but for sizes up to `u128::MAX`.
+pub const fn parse_bytes_u128(value: &str) -> Result<u128, ParseErrorKind> {
+    let value = value.as_bytes();
+    if skip_ascii_whitespace(value, 0) == value.len() {
+        return Err(ParseErrorKind::Empty);
+    }
+
+    let (negative, number_start) = match value[0] {
+        b'-' => (true, 1),
+        b'+' => (false, 1),
+        _ => (false, 0),
+    };
+    let integer_end = skip_ascii_digits(value, number_start);
+    let (fraction_start, fraction_end) = if integer_end < value.len() && value[integer_end] == b'.'
+    {
+        (integer_end + 1, skip_ascii_digits(value, integer_end + 1))
+    } else {
+        (integer_end, integer_end)
+    };
+    if integer_end == number_start && fraction_end == fraction_start {
+        return Err(ParseErrorKind::InvalidNumber);
+    }
+    if negative {
+        return Err(ParseErrorKind::Negative);
+    }
+
+    let unit_start = skip_ascii_whitespace(value, fraction_end);
+    let mut unit_end = unit_start;
+    while unit_end < value.len() && value[unit_end].is_ascii_alphabetic() {
+        unit_end += 1;
+    }
+    let Some((multiplier, divisor)) = unit_scale(value, unit_start, unit_end) else {
+        return Err(ParseErrorKind::UnknownUnit);
+    };
+    if skip_ascii_whitespace(value, unit_end) != value.len() {
+        return Err(ParseErrorKind::TrailingGarbage);
+    }
+
+    let mut bytes: u128 = 0;
+    let mut index = number_start;
+    while index < integer_end {
+        let digit = (value[index] - b'0') as u128;
+        let Some(shifted) = bytes.checked_mul(10) else {
+            return Err(ParseErrorKind::Overflow);
+        };
+        let Some(sum) = shifted.checked_add(digit) else {
+            return Err(ParseErrorKind::Overflow);
+        };
+        bytes = sum;
+        index += 1;
+    }
+    let Some(bytes) = bytes.checked_mul(multiplier) else {
+        return Err(ParseErrorKind::Overflow);
+    };
+
+    // `floor(0.fraction * multiplier)`, accumulated from the least significant digit like `parse`
+    let mut fraction_bytes: u128 = 0;
+    let mut index = fraction_end;
+    while index > fraction_start {
+        index -= 1;
+        fraction_bytes = ((value[index] - b'0') as u128 * multiplier + fraction_bytes) / 10;
+    }
+    let Some(bytes) = bytes.checked_add(fraction_bytes) else {
+        return Err(ParseErrorKind::Overflow);
+    };
+    Ok(bytes / divisor)
+}
+
+/// Returns the index of the first byte from `start` on that is not ASCII whitespace.
+const fn skip_ascii_whitespace(value: &[u8], mut start: usize) -> usize {
+    while start < value.len() && value[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    start
+}
+
+/// Returns the index of the first byte from `start` on that is not an ASCII digit.
+const fn skip_ascii_digits(value: &[u8], mut start: usize) -> usize {
+    while start < value.len() && value[start].is_ascii_digit() {
+        start += 1;
+    }
+    start
+}
+
+/// Returns the multiplier and divisor converting a number into bytes for the unit in
+/// `value[start..end]`, accepting the same units as [`Unit::from_str`].
+const fn unit_scale(value: &[u8], start: usize, end: usize) -> Option<(u128, u128)> {
+    if start == end {
+        return Some((1, 1));
+    }
+    match find_unit(value, start, end) {
+        Some((_, multiplier, divisor)) => Some((multiplier, divisor)),
+        None => None,
+    }
+}
+
+/// Looks up the unit named `value[start..end]`, ignoring ASCII case, along with the multiplier and
+/// divisor converting a number in that unit into bytes.
+///
+/// Accepts symbols, bare prefixes of byte units such as `k` or `Ki`, and long names in singular or
+/// plural. This single table serves both [`Unit::from_str`] and the `const` parser, so that they
+/// cannot drift apart.
+const fn find_unit(value: &[u8], start: usize, end: usize) -> Option<(Unit, u128, u128)> {
+    if start == end {
+        return None;
+    }
+    let tables = [
+        (Unit::SI_BYTES, KB as u128, 1),
+        (Unit::IEC_BYTES, KIB as u128, 1),
+        (Unit::SI_BITS, KB as u128, 8),
+        (Unit::IEC_BITS, KIB as u128, 8),
+    ];
+    let mut table = 0;
+    while table < tables.len() {
+        let (units, base, divisor) = tables[table];
+        let mut exp = 0;
+        while exp < units.len() {
+            let (symbol, plural) = (units[exp].symbol(), LongNames::plural(units[exp]));
+            if eq_ignore_ascii_case(value, start, end, symbol, symbol.len())
+                || (divisor == 1
+                    && eq_ignore_ascii_case(value, start, end, symbol, symbol.len() - 1))
+                || eq_ignore_ascii_case(value, start, end, plural, plural.len())
+                || eq_ignore_ascii_case(value, start, end, plural, plural.len() - 1)
+            {
+                return Some((units[exp], base.pow(exp as u32), divisor));
+            }
+            exp += 1;
+        }
+        table += 1;
+    }
+    None
+}
+
+/// Whether `value[start..end]` equals the first `len` bytes of `name`, ignoring ASCII case.
+const fn eq_ignore_ascii_case(
+    value: &[u8],
+    start: usize,
+    end: usize,
+    name: &str,
+    len: usize,
+) -> bool {
+    let name = name.as_bytes();
+    if end - start != len {
+        return false;
+    }
+    let mut index = 0;
+    while index < len {
+        if !value[start + index].eq_ignore_ascii_case(&name[index]) {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Support for the `bytes!` macro, which parses with [`parse_bytes_u128`] in a `const` item and
+/// narrows the result to the integer type expected at the call site.
+#[doc(hidden)]
+pub mod __private {
+    /// A number of bytes known at compile time.
+    pub struct Bytes<const N: u128>;
+
+    /// Converts [`Bytes`] into the integer type `T`, failing to compile if it does not fit.
+    ///
+    /// The check is an associated `const`, so that unlike an out of range literal it cannot be
+    /// silenced by allowing `overflowing_literals` or capping lints.
+    pub trait Narrow<T> {
+        const VALUE: T;
+    }
+
+    macro_rules! impl_narrow {
+        ($($ty:ty),*) => {
+            $(
+                impl<const N: u128> Narrow<$ty> for Bytes<N> {
+                    const VALUE: $ty = {
+                        assert!(N <= <$ty>::MAX as u128, "byte size does not fit into the type");
+                        N as $ty
+                    };
+                }
+            )*
+        };
+    }
+
+    impl_narrow!(u8, u16, u32, u64, u128, usize);
+}
+
+/// Parses a rate such as `"12.4 MiB/s"`, `"99.2 Mbit/s"` or `"100 Mbps"` into bytes per second.
+///
+/// The size is parsed like [`parse`], followed by an optional `/s` or `ps`. Rates in bits are
+/// recognized by a `bit` unit, or by a lowercase `b` right before `/s` or `ps` as in `Mb/s`, and
+/// rounded down to whole bytes.
+pub fn parse_rate<T: ByteInt>(value: &str) -> Result<T, ParseError> {
+    let trimmed = value.trim_end();
+    let size = trimmed
+        .strip_suffix("/s")
+        .or_else(|| trimmed.strip_suffix("ps"));
+    match size {
+        Some(size) if size.ends_with('b') && !size.ends_with("bit") => {
+            let bits = parse::<u128>(&size[..size.len() - 1])?;
+            T::from_u128(bits / 8)
+                .ok_or_else(|| ParseError::new(ParseErrorKind::Overflow, 0..value.len()))
+        }
+        size => parse(size.unwrap_or(trimmed)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Byte,
+    // power of tens
+    KiloByte,
+    MegaByte,
+    GigaByte,
+    TeraByte,
+    PetaByte,
+    ExaByte,
+    ZettaByte,
+    YottaByte,
+    RonnaByte,
+    QuettaByte,
+    // power of twos
+    KibiByte,
+    MebiByte,
+    GibiByte,
+    TebiByte,
+    PebiByte,
+    ExbiByte,
+    ZebiByte,
+    YobiByte,
+    RobiByte,
+    QuebiByte,
+    Bit,
+    // power of tens
+    KiloBit,
+    MegaBit,
+    GigaBit,
+    TeraBit,
+    PetaBit,
+    ExaBit,
+    ZettaBit,
+    YottaBit,
+    RonnaBit,
+    QuettaBit,
+    // power of twos
+    KibiBit,
+    MebiBit,
+    GibiBit,
+    TebiBit,
+    PebiBit,
+    ExbiBit,
+    ZebiBit,
+    YobiBit,
+    RobiBit,
+    QuebiBit,
+}
+
+impl Unit {
+    const SI_BYTES: [Unit; 11] = [
+        Unit::Byte,
+        Unit::KiloByte,
+        Unit::MegaByte,
+        Unit::GigaByte,
+        Unit::TeraByte,
+        Unit::PetaByte,
+        Unit::ExaByte,
+        Unit::ZettaByte,
+        Unit::YottaByte,
+        Unit::RonnaByte,
+        Unit::QuettaByte,
+    ];
+    const IEC_BYTES: [Unit; 11] = [
+        Unit::Byte,
+        Unit::KibiByte,
+        Unit::MebiByte,
+        Unit::GibiByte,
+        Unit::TebiByte,
+        Unit::PebiByte,
+        Unit::ExbiByte,
+        Unit::ZebiByte,
+        Unit::YobiByte,
+        Unit::RobiByte,
+        Unit::QuebiByte,
+    ];
+    const SI_BITS: [Unit; 11] = [
+        Unit::Bit,
+        Unit::KiloBit,
+        Unit::MegaBit,
+        Unit::GigaBit,
+        Unit::TeraBit,
+        Unit::PetaBit,
+        Unit::ExaBit,
+        Unit::ZettaBit,
+        Unit::YottaBit,
+        Unit::RonnaBit,
+        Unit::QuettaBit,
+    ];
+    const IEC_BITS: [Unit; 11] = [
+        Unit::Bit,
+        Unit::KibiBit,
+        Unit::MebiBit,
+        Unit::GibiBit,
+        Unit::TebiBit,
+        Unit::PebiBit,
+        Unit::ExbiBit,
+        Unit::ZebiBit,
+        Unit::YobiBit,
+        Unit::RobiBit,
+        Unit::QuebiBit,
+    ];
+
+    /// Returns the canonical symbol of the unit, e.g. `kB`, `MiB` or `Mbit`.
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Unit::Byte => "B",
+            // power of tens
+            Unit::KiloByte => "kB",
+            Unit::MegaByte => "MB",
+            Unit::GigaByte => "GB",
+            Unit::TeraByte => "TB",
+            Unit::PetaByte => "PB",
+            Unit::ExaByte => "EB",
+            Unit::ZettaByte => "ZB",
+            Unit::YottaByte => "YB",
+            Unit::RonnaByte => "RB",
+            Unit::QuettaByte => "QB",
+            // power of twos
+            Unit::KibiByte => "KiB",
+            Unit::MebiByte => "MiB",
+            Unit::GibiByte => "GiB",
+            Unit::TebiByte => "TiB",
+            Unit::PebiByte => "PiB",
+            Unit::ExbiByte => "EiB",
+            Unit::ZebiByte => "ZiB",
+            Unit::YobiByte => "YiB",
+            Unit::RobiByte => "RiB",
+            Unit::QuebiByte => "QiB",
+            Unit::Bit => "bit",
+            // power of tens
+            Unit::KiloBit => "kbit",
+            Unit::MegaBit => "Mbit",
+            Unit::GigaBit => "Gbit",
+            Unit::TeraBit => "Tbit",
+            Unit::PetaBit => "Pbit",
+            Unit::ExaBit => "Ebit",
+            Unit::ZettaBit => "Zbit",
+            Unit::YottaBit => "Ybit",
+            Unit::RonnaBit => "Rbit",
+            Unit::QuettaBit => "Qbit",
+            // power of twos
+            Unit::KibiBit => "Kibit",
+            Unit::MebiBit => "Mibit",
+            Unit::GibiBit => "Gibit",
+            Unit::TebiBit => "Tibit",
+            Unit::PebiBit => "Pibit",
+            Unit::ExbiBit => "Eibit",
+            Unit::ZebiBit => "Zibit",
+            Unit::YobiBit => "Yibit",
+            Unit::RobiBit => "Ribit",
+            Unit::QuebiBit => "Qibit",
+        }
+    }
+
+    /// Whether the unit has a decimal (SI) prefix, e.g. `kB` or `Mbit`.
+    pub fn is_si(self) -> bool {
+        Self::SI_BYTES[1..].contains(&self) || Self::SI_BITS[1..].contains(&self)
+    }
+
+    /// Returns the unit with the binary prefix of the same name, e.g. [`Unit::KibiByte`] for
+    /// [`Unit::KiloByte`].
+    fn to_binary(self) -> Unit {
+        match Self::SI_BYTES.iter().position(|unit| *unit == self) {
+            Some(exp) => Self::IEC_BYTES[exp],
+            None => match Self::SI_BITS.iter().position(|unit| *unit == self) {
+                Some(exp) => Self::IEC_BITS[exp],
+                None => self,
+            },
+        }
+    }
+
+    /// Whether `unit` is the symbol of the unit, where kilo may also be written `K`, or with `bare`
+    /// the prefix alone of a decimal byte unit, e.g. `K` or `M`.
+    fn is_spelled_by(self, unit: &str, bare: bool) -> bool {
+        let symbol = self.symbol();
+        let (unit, symbol) = match (unit.strip_prefix('K'), symbol.strip_prefix('k')) {
+            (Some(unit), Some(symbol)) => (unit, symbol),
+            _ => (unit, symbol),
+        };
+        unit == symbol
+            || bare
+                && self.is_si()
+                && !unit.starts_with('k')
+                && symbol.strip_suffix('B') == Some(unit)
+    }
+
+    /// Whether the unit counts bits rather than bytes.
+    pub fn is_bit(self) -> bool {
+        Self::SI_BITS.contains(&self) || Self::IEC_BITS.contains(&self)
+    }
+
+    /// Returns the number of bits in the unit.
+    pub fn bits(self) -> u128 {
+        match self {
+            Unit::Bit => 1,
+            // power of tens
+            Unit::KiloBit => KB as u128,
+            Unit::MegaBit => MB as u128,
+            Unit::GigaBit => GB as u128,
+            Unit::TeraBit => TB as u128,
+            Unit::PetaBit => PB as u128,
+            Unit::ExaBit => EB as u128,
+            Unit::ZettaBit => ZB,
+            Unit::YottaBit => YB,
+            Unit::RonnaBit => RB,
+            Unit::QuettaBit => QB,
+            // power of twos
+            Unit::KibiBit => KIB as u128,
+            Unit::MebiBit => MIB as u128,
+            Unit::GibiBit => GIB as u128,
+            Unit::TebiBit => TIB as u128,
+            Unit::PebiBit => PIB as u128,
+            Unit::ExbiBit => EIB as u128,
+            Unit::ZebiBit => ZIB,
+            Unit::YobiBit => YIB,
+            Unit::RobiBit => RIB,
+            Unit::QuebiBit => QIB,
+            // units of bytes
+            unit => u128::from(unit) * 8,
+        }
+    }
+
+    /// Parses a unit with case-sensitive symbols, telling bits and bytes apart.
+    ///
+    /// A trailing `B` means bytes, while `b` or `bit` means bits, e.g. `MB` is a megabyte and `Mb` or
+    /// `Mbit` a megabit. Prefixes are uppercase, except for kilo which may also be written `k`, and
+    /// binary prefixes append an `i`, e.g. `KiB` or `Mib`. A bare prefix such as `M` means bytes.
+    pub fn from_str_case_sensitive(unit: &str) -> Result<Self, ParseError> {
+        let error = || ParseError::new(ParseErrorKind::UnknownUnit, 0..unit.len());
+        let (prefix, bits) = if let Some(prefix) = unit.strip_suffix("bit") {
+            (prefix, true)
+        } else if let Some(prefix) = unit.strip_suffix('b') {
+            (prefix, true)
+        } else {
+            (unit.strip_suffix('B').unwrap_or(unit), false)
+        };
+        let (prefix, binary) = match prefix.strip_suffix('i') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+        let exp = match prefix {
+            "" if !binary && !unit.is_empty() => 0,
+            "k" if !binary => 1,
+            "K" => 1,
+            "M" => 2,
+            "G" => 3,
+            "T" => 4,
+            "P" => 5,
+            "E" => 6,
+            "Z" => 7,
+            "Y" => 8,
+            "R" => 9,
+            "Q" => 10,
+            _ => return Err(error()),
+        };
+        Ok(Self::table(binary, bits)[exp])
+    }
+
+    /// Returns the units with binary or decimal prefixes, of bits or bytes, indexed by the power of
+    /// the prefix.
+    fn table(binary: bool, bits: bool) -> [Unit; 11] {
+        match (binary, bits) {
+            (false, false) => Self::SI_BYTES,
+            (true, false) => Self::IEC_BYTES,
+            (false, true) => Self::SI_BITS,
+            (true, true) => Self::IEC_BITS,
+        }
+    }
+}
+
+/// Returns the number of bytes in the unit.
+///
+/// A single [`Unit::Bit`] is an eighth of a byte and rounds down to zero, see [`Unit::bits`] for an
+/// exact value.
+impl From<Unit> for u128 {
+    fn from(unit: Unit) -> u128 {
+        match unit {
+            Unit::Byte => B as u128,
+            // power of tens
+            Unit::KiloByte => KB as u128,
+            Unit::MegaByte => MB as u128,
+            Unit::GigaByte => GB as u128,
+            Unit::TeraByte => TB as u128,
+            Unit::PetaByte => PB as u128,
+            Unit::ExaByte => EB as u128,
+            Unit::ZettaByte => ZB,
+            Unit::YottaByte => YB,
+            Unit::RonnaByte => RB,
+            Unit::QuettaByte => QB,
+            // power of twos
+            Unit::KibiByte => KIB as u128,
+            Unit::MebiByte => MIB as u128,
+            Unit::GibiByte => GIB as u128,
+            Unit::TebiByte => TIB as u128,
+            Unit::PebiByte => PIB as u128,
+            Unit::ExbiByte => EIB as u128,
+            Unit::ZebiByte => ZIB,
+            Unit::YobiByte => YIB,
+            Unit::RobiByte => RIB,
+            Unit::QuebiByte => QIB,
+            // units of bits
+            unit => unit.bits() / 8,
+        }
+    }
+}
+
+/// Fails for units of a zettabyte or zebibyte and above, which do not fit into `u64`.
+impl TryFrom<Unit> for u64 {
+    type Error = core::num::TryFromIntError;
+
+    fn try_from(unit: Unit) -> Result<u64, Self::Error> {
+        u64::try_from(u128::from(unit))
+    }
+}
+
+impl FromStr for Unit {
+    type Err = ParseError;
+
+    fn from_str(unit: &str) -> Result<Self, Self::Err> {
+        match find_unit(unit.as_bytes(), 0, unit.len()) {
+            Some((unit, ..)) => Ok(unit),
+            None => Err(ParseError::new(ParseErrorKind::UnknownUnit, 0..unit.len())),
+        }
+    }
+}
+
+/// A signed difference between two byte sizes, e.g. the growth of a disk since yesterday.
+///
+/// Displays with an explicit sign, such as `+1.2 GiB` or `-300 B`, and parses the same strings. Like
+/// the derived types, `Display` honors the precision, `{:#}` for SI units, and width, fill and
+/// alignment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteDelta(pub i128);
+
+impl ByteDelta {
+    /// Returns `to - from`.
+    ///
+    /// Panics if the difference does not fit into an `i128`, which is only possible for `u128`
+    /// sizes.
+    pub fn between<T: ByteInt>(from: T, to: T) -> Self {
+        Self::checked_between(from, to).expect("byte delta overflows i128")
+    }
+
+    /// Returns `to - from`, or `None` if the difference does not fit into an `i128`.
+    pub fn checked_between<T: ByteInt>(from: T, to: T) -> Option<Self> {
+        let (from, to) = (from.into_u128(), to.into_u128());
+        let delta = if to >= from {
+            i128::try_from(to - from).ok()?
+        } else {
+            negate(from - to)?
+        };
+        Some(ByteDelta(delta))
+    }
+
+    /// Returns the signed number of bytes.
+    pub const fn as_i128(&self) -> i128 {
+        self.0
+    }
+
+    /// Returns the number of bytes without the sign.
+    pub const fn magnitude(&self) -> u128 {
+        self.0.unsigned_abs()
+    }
+
+    /// Returns `true` if the delta is a decrease.
+    pub const fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    /// Returns `-self`, or `None` for `ByteDelta(i128::MIN)`, whose negation does not fit.
+    pub const fn checked_neg(self) -> Option<Self> {
+        match self.0.checked_neg() {
+            Some(delta) => Some(ByteDelta(delta)),
+            None => None,
+        }
+    }
+
+    /// Returns `self + rhs`, or `None` if the sum does not fit into an `i128`.
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(delta) => Some(ByteDelta(delta)),
+            None => None,
+        }
+    }
+
+    /// Returns `self - rhs`, or `None` if the difference does not fit into an `i128`.
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(delta) => Some(ByteDelta(delta)),
+            None => None,
+        }
+    }
+
+    /// Returns the delta as a string such as `+1.2 GiB` or `-512 B`, without a sign when zero.
+    #[cfg(feature = "alloc")]
+    pub fn to_string_as(&self, format: Format) -> String {
+        format!("{}{}", self.sign(), to_string(self.magnitude(), format))
+    }
+
+    fn sign(&self) -> &'static str {
+        match self.0 {
+            0 => "",
+            delta if delta < 0 => "-",
+            _ => "+",
+        }
+    }
+}
+
+/// Returns `-magnitude`, or `None` if it does not fit into an `i128`.
+fn negate(magnitude: u128) -> Option<i128> {
+    if magnitude == i128::MIN.unsigned_abs() {
+        Some(i128::MIN)
+    } else {
+        i128::try_from(magnitude).ok().map(|magnitude| -magnitude)
+    }
+}
+
+impl core::fmt::Display for ByteDelta {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let format = if f.alternate() {
+            Format::SI
+        } else {
+            Format::IEC
+        };
+        let precision = f.precision().unwrap_or(1);
+        let delta = FormattedSize::build(|buffer| {
+            core::fmt::Write::write_str(buffer, self.sign())?;
+            write_to(buffer, self.magnitude(), format, precision)
+        });
+        pad(f, delta.as_str())
+    }
+}
+
+impl FromStr for ByteDelta {
+    type Err = ParseError;
+
+    /// Parses a size with an optional sign, e.g. `"+1.5 GiB"` or `"-300 B"`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (negative, magnitude) = match value.as_bytes().first() {
+            Some(b'-') => (true, &value[1..]),
+            Some(b'+') => (false, &value[1..]),
+            _ => (false, value),
+        };
+        let offset = value.len() - magnitude.len();
+        if matches!(magnitude.as_bytes().first(), Some(b'-' | b'+')) {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidNumber,
+                offset..offset + 1,
+            ));
+        }
+        let bytes = parse::<u128>(magnitude).map_err(|error| error.offset(offset))?;
+        let delta = if negative {
+            negate(bytes)
+        } else {
+            i128::try_from(bytes).ok()
+        };
+        delta
+            .map(ByteDelta)
+            .ok_or_else(|| ParseError::new(ParseErrorKind::Overflow, 0..value.len()))
+    }
+}
+
+/// Panics for `ByteDelta(i128::MIN)`, whose negation does not fit into an `i128`, see
+/// [`ByteDelta::checked_neg`].
+impl core::ops::Neg for ByteDelta {
+    type Output = ByteDelta;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("byte delta overflows i128")
+    }
+}
+
+/// Panics if the sum does not fit into an `i128`, see [`ByteDelta::checked_add`].
+impl core::ops::Add for ByteDelta {
+    type Output = ByteDelta;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("byte delta overflows i128")
+    }
+}
+
+/// Panics if the difference does not fit into an `i128`, see [`ByteDelta::checked_sub`].
+impl core::ops::Sub for ByteDelta {
+    type Output = ByteDelta;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("byte delta overflows i128")
+    }
+}
+
+/// Summary statistics over a collection of sizes, see [`stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stats<T> {
+    /// The number of sizes.
+    pub count: usize,
+    /// The sum of all sizes.
+    pub total: T,
+    /// The smallest size.
+    pub min: T,
+    /// The largest size.
+    pub max: T,
+    /// The arithmetic mean, rounded down.
+    pub mean: T,
+    /// The middle size, or the mean of the two middle sizes rounded down for an even count.
+    pub median: T,
+}
+
+/// Computes [`Stats`] over `sizes`, accepting both sizes and references to sizes.
+///
+/// Returns `None` if `sizes` is empty or their total does not fit into the inner type of `T`.
+#[cfg(feature = "alloc")]
+pub fn stats<T, I>(sizes: I) -> Option<Stats<T>>
+where
+    T: ByteNewType,
+    I: IntoIterator,
+    I::Item: core::borrow::Borrow<T>,
+{
+    use core::borrow::Borrow;
+
+    let mut values: Vec<T::Inner> = sizes
+        .into_iter()
+        .map(|size| size.borrow().inner())
+        .collect();
+    values.sort_unstable();
+    let count = values.len();
+    let (min, max) = (*values.first()?, values[count - 1]);
+
+    let total = values
+        .iter()
+        .try_fold(0u128, |total, value| total.checked_add(value.into_u128()))?;
+    let mean = total / count as u128;
+    let median = if count % 2 == 1 {
+        values[count / 2].into_u128()
+    } else {
+        let (lower, upper) = (
+            values[count / 2 - 1].into_u128(),
+            values[count / 2].into_u128(),
+        );
+        lower + (upper - lower) / 2
+    };
+
+    Some(Stats {
+        count,
+        total: T::from_inner(T::Inner::from_u128(total)?),
+        min: T::from_inner(min),
+        max: T::from_inner(max),
+        mean: T::from_inner(T::Inner::from_u128(mean)?),
+        median: T::from_inner(T::Inner::from_u128(median)?),
+    })
+}
+
+/// A range of sizes with inclusive, exclusive or unbounded ends, e.g. to bucket files by size.
+///
+/// As sizes are whole numbers of bytes, an exclusive start is stored as the inclusive start one byte
+/// later, and `..1024` contains the same sizes as `..=1023`. Equality compares the bounds as written.
+///
+/// `Display` writes Rust range syntax, e.g. `1.0 MiB..=4.0 GiB` or `..1.0 KiB`, passing the
+/// precision and `{:#}` on to the bounds, so that `{:.0}` writes `1 MiB..=4 GiB`. `FromStr` parses
+/// the same syntax as well as comparisons such as `>=500M` or `<4k`, and with the `serde` feature
+/// ranges (de)serialize as such strings.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HumanByteRange<T: ByteNewType> {
+    start: core::ops::Bound<T>,
+    end: core::ops::Bound<T>,
+}
+
+impl<T: ByteNewType> HumanByteRange<T> {
+    /// Creates a range including both `start` and `stop`, where `None` leaves that end unbounded.
+    pub fn new<I: Into<T>>(start: Option<I>, stop: Option<I>) -> Self {
+        use core::ops::Bound;
+
+        HumanByteRange {
+            start: start.map_or(Bound::Unbounded, |start| Bound::Included(start.into())),
+            end: stop.map_or(Bound::Unbounded, |stop| Bound::Included(stop.into())),
+        }
+    }
+
+    /// Creates a range from arbitrary bounds, e.g. `(Bound::Excluded(a), Bound::Unbounded)`.
+    pub fn from_bounds(start: core::ops::Bound<T>, end: core::ops::Bound<T>) -> Self {
+        use core::ops::Bound;
+
+        let start = match start {
+            Bound::Excluded(start) => match start.inner().into_u128().checked_add(1) {
+                Some(next) if next <= T::Inner::MAX.into_u128() => {
+                    Bound::Included(Self::size(next))
+                }
+                // nothing follows the largest size
+                _ => {
+                    return HumanByteRange {
+                        start: Bound::Included(start),
+                        end: Bound::Excluded(T::from_inner(T::Inner::MAX)),
+                    }
+                }
+            },
+            start => start,
+        };
+        HumanByteRange { start, end }
+    }
+
+    /// Returns `true` if `size` lies within the range.
+    pub fn contains(&self, size: &T) -> bool {
+        let size = size.inner().into_u128();
+        matches!(self.inclusive(), Some((first, last)) if first <= size && size <= last)
+    }
+
+    /// Returns `true` if the range contains no sizes, e.g. `2 KiB..1 KiB` or `1 KiB..1 KiB`.
+    pub fn is_empty(&self) -> bool {
+        self.inclusive().is_none()
+    }
+
+    /// Returns the number of sizes in the range, e.g. `4 GiB - 1 MiB` for `1 MiB..4 GiB`.
+    ///
+    /// Returns `None` only if the range covers every value of `T`, as that count is one more than the
+    /// largest `T`.
+    pub fn len(&self) -> Option<T> {
+        let length = match self.inclusive() {
+            Some((first, last)) => (last - first).checked_add(1)?,
+            None => 0,
+        };
+        T::Inner::from_u128(length).map(T::from_inner)
+    }
+
+    /// Returns the sizes in both ranges, which may be empty.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let start = if self.start_key() >= other.start_key() {
+            &self.start
+        } else {
+            &other.start
+        };
+        let end = if self.end_key() <= other.end_key() {
+            &self.end
+        } else {
+            &other.end
+        };
+        HumanByteRange {
+            start: Self::copy(start),
+            end: Self::copy(end),
+        }
+    }
+
+    /// Returns the sizes in either range, or `None` if the ranges neither overlap nor are adjacent,
+    /// so that the result would not be a single range.
+    ///
+    /// For example, `..=1023` and `1 KiB..2 KiB` join into `..2 KiB`.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        let ((first, last), (other_first, other_last)) = match (self.inclusive(), other.inclusive())
+        {
+            (Some(bounds), Some(other_bounds)) => (bounds, other_bounds),
+            (None, _) => return Some(Self::copy_range(other)),
+            (_, None) => return Some(Self::copy_range(self)),
+        };
+        // ranges are adjacent when one starts right after the other ends
+        let disjoint =
+            |last: u128, first: u128| matches!(last.checked_add(1), Some(next) if next < first);
+        if disjoint(last, other_first) || disjoint(other_last, first) {
+            return None;
+        }
+        let start = if self.start_key() <= other.start_key() {
+            &self.start
+        } else {
+            &other.start
+        };
+        let end = if self.end_key() >= other.end_key() {
+            &self.end
+        } else {
+            &other.end
+        };
+        Some(HumanByteRange {
+            start: Self::copy(start),
+            end: Self::copy(end),
+        })
+    }
+
+    /// Returns the `(offset, len)` of consecutive chunks of `size` bytes covering the range, the last
+    /// of which may be shorter, e.g. to split an upload into parts.
+    ///
+    /// An unbounded end covers all sizes of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn chunks(&self, size: T) -> Chunks<T> {
+        let size = size.inner().into_u128();
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks {
+            range: self.inclusive(),
+            size,
+            marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the sizes from the start of the range on, `step` bytes apart, that lie within the
+    /// range, e.g. `0 B`, `1.0 MiB` and `2.0 MiB` for `..=2 MiB` in steps of `1 MiB`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn step_by(&self, step: T) -> StepBy<T> {
+        let step = step.inner().into_u128();
+        assert!(step != 0, "step must be non-zero");
+        StepBy {
+            range: self.inclusive(),
+            step,
+            marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the `(offset, len)` of `parts` consecutive pieces of nearly equal length covering the
+    /// range, the first ones being a byte longer if the length does not divide evenly.
+    ///
+    /// Pieces are never empty, so a range holding fewer bytes than `parts` yields one piece per
+    /// byte, and an empty range none.
+    ///
+    /// Returns `None` if the length of a piece does not fit into `T`, which only happens when a range
+    /// covering every value of `T` is split into a single piece.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parts` is zero.
+    pub fn split_into(&self, parts: usize) -> Option<SplitInto<T>> {
+        assert!(parts != 0, "number of parts must be non-zero");
+        let (offset, count, base, longer) = match self.inclusive() {
+            Some((first, last)) => {
+                // the length `last - first + 1` may not fit into a `u128`, so divide `last - first`
+                let span = last - first;
+                let count = (parts as u128).min(span.saturating_add(1));
+                let (quotient, remainder) = (span / count, span % count);
+                let (base, longer) = match remainder + 1 == count {
+                    true => (quotient.checked_add(1)?, 0),
+                    false => (quotient, remainder + 1),
+                };
+                T::Inner::from_u128(base + u128::from(longer != 0))?;
+                (first, count, base, longer)
+            }
+            None => (0, 0, 0, 0),
+        };
+        Some(SplitInto {
+            offset,
+            count,
+            base,
+            longer,
+            marker: core::marker::PhantomData,
+        })
+    }
+
+    /// Returns the first and last size in the range, or `None` if it is empty.
+    fn inclusive(&self) -> Option<(u128, u128)> {
+        use core::ops::Bound;
+
+        let first = match &self.start {
+            Bound::Included(start) => start.inner().into_u128(),
+            // normalized away by `from_bounds`
+            Bound::Excluded(start) => start.inner().into_u128().checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let last = match &self.end {
+            Bound::Included(end) => end.inner().into_u128(),
+            Bound::Excluded(end) => end.inner().into_u128().checked_sub(1)?,
+            Bound::Unbounded => T::Inner::MAX.into_u128(),
+        };
+        (first <= last).then_some((first, last))
+    }
+
+    /// Orders starts by the sizes they admit, an exclusive start coming after an inclusive one.
+    fn start_key(&self) -> (u128, bool) {
+        use core::ops::Bound;
+
+        match &self.start {
+            Bound::Included(start) => (start.inner().into_u128(), false),
+            Bound::Excluded(start) => (start.inner().into_u128(), true),
+            Bound::Unbounded => (0, false),
+        }
+    }
+
+    /// Orders ends by the sizes they admit, an exclusive end coming before an inclusive one.
+    fn end_key(&self) -> (u128, bool) {
+        use core::ops::Bound;
+
+        match &self.end {
+            Bound::Included(end) => (end.inner().into_u128(), true),
+            Bound::Excluded(end) => (end.inner().into_u128(), false),
+            Bound::Unbounded => (T::Inner::MAX.into_u128(), true),
+        }
+    }
+
+    /// Writes the range in Rust syntax, writing each bound with `bound`.
+    fn write_bounds<W: core::fmt::Write + ?Sized>(
+        &self,
+        out: &mut W,
+        mut bound: impl FnMut(&mut W, &T) -> core::fmt::Result,
+    ) -> core::fmt::Result {
+        use core::ops::Bound;
+
+        if let Bound::Included(start) | Bound::Excluded(start) = &self.start {
+            bound(out, start)?;
+        }
+        match &self.end {
+            Bound::Included(end) => {
+                out.write_str("..=")?;
+                bound(out, end)
+            }
+            Bound::Excluded(end) => {
+                out.write_str("..")?;
+                bound(out, end)
+            }
+            Bound::Unbounded => out.write_str(".."),
+        }
+    }
+
+    fn size(bytes: u128) -> T {
+        T::from_inner(T::Inner::from_u128(bytes).expect("size fits into the inner type"))
+    }
+
+    fn copy(bound: &core::ops::Bound<T>) -> core::ops::Bound<T> {
+        use core::ops::Bound;
+
+        match bound {
+            Bound::Included(size) => Bound::Included(T::from_inner(size.inner())),
+            Bound::Excluded(size) => Bound::Excluded(T::from_inner(size.inner())),
+            Bound::Unbounded => Bound::Unbounded,
+        }
+    }
+
+    fn copy_range(range: &Self) -> Self {
+        HumanByteRange {
+            start: Self::copy(&range.start),
+            end: Self::copy(&range.end),
+        }
+    }
+}
+
+impl<T: ByteNewType> From<core::ops::Range<T>> for HumanByteRange<T> {
+    fn from(range: core::ops::Range<T>) -> Self {
+        use core::ops::Bound;
+
+        Self::from_bounds(Bound::Included(range.start), Bound::Excluded(range.end))
+    }
+}
+
+impl<T: ByteNewType> From<core::ops::RangeInclusive<T>> for HumanByteRange<T> {
+    fn from(range: core::ops::RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Self::new(Some(start), Some(end))
+    }
+}
+
+impl<T: ByteNewType> From<core::ops::RangeFrom<T>> for HumanByteRange<T> {
+    fn from(range: core::ops::RangeFrom<T>) -> Self {
+        Self::new(Some(range.start), None)
+    }
+}
+
+impl<T: ByteNewType> From<core::ops::RangeTo<T>> for HumanByteRange<T> {
+    fn from(range: core::ops::RangeTo<T>) -> Self {
+        use core::ops::Bound;
+
+        Self::from_bounds(Bound::Unbounded, Bound::Excluded(range.end))
+    }
+}
+
+impl<T: ByteNewType> From<core::ops::RangeToInclusive<T>> for HumanByteRange<T> {
+    fn from(range: core::ops::RangeToInclusive<T>) -> Self {
+        Self::new(None, Some(range.end))
+    }
+}
+
+impl<T: ByteNewType> From<core::ops::RangeFull> for HumanByteRange<T> {
+    fn from(_: core::ops::RangeFull) -> Self {
+        Self::new(None::<T>, None)
+    }
+}
+
+impl<T: ByteNewType + core::fmt::Display> core::fmt::Display for HumanByteRange<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // pass the precision and alternate flag on, but not the width, which applies to neither bound
+        self.write_bounds(f, |f, size| match (f.alternate(), f.precision()) {
+            (false, None) => write!(f, "{}", size),
+            (true, None) => write!(f, "{:#}", size),
+            (false, Some(precision)) => write!(f, "{:.*}", precision, size),
+            (true, Some(precision)) => write!(f, "{:#.*}", precision, size),
+        })
+    }
+}
+
+impl<T: ByteNewType + core::fmt::Display> core::fmt::Debug for HumanByteRange<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl<T> FromStr for HumanByteRange<T>
+where
+    T: ByteNewType + FromStr<Err = ParseError>,
+{
+    type Err = ParseError;
+
+    /// Parses Rust range syntax such as `10 MiB..1 GiB`, `1 KiB..=4 KiB`, `500M..` or `..4k`, and
+    /// comparisons such as `>=500M`, `>1 GiB`, `<=4k` or `<4k`.
+    ///
+    /// Sizes are parsed with the `FromStr` of `T`, and may be surrounded by whitespace.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        use core::ops::Bound;
+
+        // parses the size in `value[start..end]`, keeping spans relative to `value`
+        let size = |start: usize, end: usize| {
+            let text = &value[start..end];
+            let offset = start + text.len() - text.trim_start().len();
+            text.trim()
+                .parse::<T>()
+                .map_err(|error| error.offset(offset))
+        };
+        let trimmed = value.trim_start();
+        let start = value.len() - trimmed.len();
+        if trimmed.trim_end().is_empty() {
+            return Err(ParseError::new(ParseErrorKind::Empty, 0..value.len()));
+        }
+
+        for (operator, bounds) in [
+            (
+                ">=",
+                (|size| (Bound::Included(size), Bound::Unbounded)) as fn(T) -> _,
+            ),
+            ("<=", |size| (Bound::Unbounded, Bound::Included(size))),
+            (">", |size| (Bound::Excluded(size), Bound::Unbounded)),
+            ("<", |size| (Bound::Unbounded, Bound::Excluded(size))),
+        ] {
+            if trimmed.starts_with(operator) {
+                let (start, end) = bounds(size(start + operator.len(), value.len())?);
+                return Ok(Self::from_bounds(start, end));
+            }
+        }
+
+        let Some(dots) = value.find("..") else {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidRange,
+                0..value.len(),
+            ));
+        };
+        let (end_start, inclusive) = match value[dots + 2..].starts_with('=') {
+            true => (dots + 3, true),
+            false => (dots + 2, false),
+        };
+        let start_bound = match value[..dots].trim().is_empty() {
+            true => Bound::Unbounded,
+            false => Bound::Included(size(0, dots)?),
+        };
+        let end_bound = match (value[end_start..].trim().is_empty(), inclusive) {
+            (true, false) => Bound::Unbounded,
+            (true, true) => {
+                return Err(ParseError::new(
+                    ParseErrorKind::InvalidRange,
+                    dots..value.len(),
+                ))
+            }
+            (false, true) => Bound::Included(size(end_start, value.len())?),
+            (false, false) => Bound::Excluded(size(end_start, value.len())?),
+        };
+        Ok(Self::from_bounds(start_bound, end_bound))
+    }
+}
+
+/// Serialized as a string with exact IEC sizes, e.g. `"1 MiB..4 GiB"`, so that it deserializes to
+/// the same range.
+#[cfg(feature = "serde")]
+impl<T: ByteNewType> serde::Serialize for HumanByteRange<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut range = String::new();
+        self.write_bounds(&mut range, |out, size| {
+            write_exact(out, size.inner(), Format::IEC)
+        })
+        .expect("writing to a String cannot fail");
+        serializer.serialize_str(&range)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for HumanByteRange<T>
+where
+    T: ByteNewType + FromStr<Err = ParseError>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let range = String::deserialize(deserializer)?;
+        range.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Converts a number of remaining items into a `size_hint`.
+fn size_hint(remaining: Option<u128>) -> (usize, Option<usize>) {
+    match remaining.map(usize::try_from) {
+        Some(Ok(remaining)) => (remaining, Some(remaining)),
+        _ => (usize::MAX, None),
+    }
+}
+
+/// An iterator over the `(offset, len)` of chunks of a range, see [`HumanByteRange::chunks`].
+#[derive(Clone, Debug)]
+pub struct Chunks<T> {
+    /// The first and last byte not yet yielded, or `None` once done.
+    range: Option<(u128, u128)>,
+    size: u128,
+    marker: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: ByteNewType> Iterator for Chunks<T> {
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, last) = self.range?;
+        let chunk_last = first.saturating_add(self.size - 1).min(last);
+        self.range = (chunk_last < last).then(|| (chunk_last + 1, last));
+        Some((
+            HumanByteRange::<T>::size(first),
+            HumanByteRange::<T>::size(chunk_last - first + 1),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // a chunk per byte of all `u128` sizes is one more than `u128::MAX`
+        size_hint(match self.range {
+            Some((first, last)) => ((last - first) / self.size).checked_add(1),
+            None => Some(0),
+        })
+    }
+}
+
+impl<T: ByteNewType> core::iter::FusedIterator for Chunks<T> {}
+
+/// An iterator over sizes a fixed step apart within a range, see [`HumanByteRange::step_by`].
+#[derive(Clone, Debug)]
+pub struct StepBy<T> {
+    /// The next size and the last size of the range, or `None` once done.
+    range: Option<(u128, u128)>,
+    step: u128,
+    marker: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: ByteNewType> Iterator for StepBy<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (next, last) = self.range?;
+        self.range = next
+            .checked_add(self.step)
+            .filter(|following| *following <= last)
+            .map(|following| (following, last));
+        Some(HumanByteRange::<T>::size(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(match self.range {
+            Some((next, last)) => ((last - next) / self.step).checked_add(1),
+            None => Some(0),
+        })
+    }
+}
+
+impl<T: ByteNewType> core::iter::FusedIterator for StepBy<T> {}
+
+/// An iterator over the `(offset, len)` of pieces of nearly equal length covering a range, see
+/// [`HumanByteRange::split_into`].
+#[derive(Clone, Debug)]
+pub struct SplitInto<T> {
+    offset: u128,
+    /// The number of pieces left.
+    count: u128,
+    base: u128,
+    /// The number of pieces left that are a byte longer than `base`.
+    longer: u128,
+    marker: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: ByteNewType> Iterator for SplitInto<T> {
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count == 0 {
+            return None;
+        }
+        let len = match self.longer {
+            0 => self.base,
+            _ => {
+                self.longer -= 1;
+                self.base + 1
+            }
+        };
+        let offset = self.offset;
+        self.count -= 1;
+        // the end of the last piece may be one past the largest size
+        self.offset = offset.wrapping_add(len);
+        Some((
+            HumanByteRange::<T>::size(offset),
+            HumanByteRange::<T>::size(len),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(Some(self.count))
+    }
+}
+
+impl<T: ByteNewType> ExactSizeIterator for SplitInto<T> {}
+
+impl<T: ByteNewType> core::iter::FusedIterator for SplitInto<T> {}
+
+impl<T: ByteNewType> core::ops::RangeBounds<T> for HumanByteRange<T> {
     fn start_bound(&self) -> core::ops::Bound<&T> {
-        core::ops::Bound::Included(&self.start)
+        self.start.as_ref()
     }
 
     fn end_bound(&self) -> core::ops::Bound<&T> {
-        core::ops::Bound::Included(&self.stop)
+        self.end.as_ref()
     }
 }